@@ -1,5 +1,14 @@
 package com.github.milenkovicm.adhesive;
 
+import java.math.BigDecimal;
+import java.math.RoundingMode;
+import java.nio.charset.StandardCharsets;
+import java.time.Duration;
+import java.time.Instant;
+import java.time.LocalDate;
+import java.time.LocalTime;
+import java.time.Period;
+import java.time.temporal.ChronoUnit;
 import java.util.Iterator;
 import java.util.function.BiFunction;
 import org.apache.arrow.c.ArrowArray;
@@ -9,6 +18,9 @@ import org.apache.arrow.memory.BufferAllocator;
 import org.apache.arrow.memory.RootAllocator;
 import org.apache.arrow.vector.*;
 import org.apache.arrow.vector.table.Row;
+import org.apache.arrow.vector.types.TimeUnit;
+import org.apache.arrow.vector.types.pojo.ArrowType;
+import org.apache.arrow.vector.types.pojo.Field;
 import org.apache.arrow.vector.table.Table;
 import org.slf4j.Logger;
 import org.slf4j.LoggerFactory;
@@ -24,16 +36,19 @@ import org.slf4j.LoggerFactory;
  * @link "https://arrow.apache.org/docs/java/table.html"
  * @link "https://docs.rs/arrow/latest/arrow/ffi/index.html"
  */
-public abstract class Adhesive {
+public abstract class Adhesive implements AdhesiveFunction {
 
   static final Logger logger = LoggerFactory.getLogger(Adhesive.class);
 
   static final BufferAllocator allocator = new RootAllocator();
 
+  private ErrorPolicy errorPolicy = ErrorPolicy.FAIL;
+
   /**
    * Computation method
    *
-   * <p>Implementors should not access fields by name.
+   * <p>Implementors should not access fields by name. Null arguments can be checked with {@link
+   * Row#isNull(int)}.
    *
    * @param row
    * @return value or null unless used by computeNonNull methods
@@ -41,7 +56,63 @@ public abstract class Adhesive {
    */
   protected abstract <T> T compute(Row row);
 
-  private <T extends FixedWidthVector & FieldVector> void computeInternal(
+  /**
+   * Strict functions are not invoked for rows with null arguments, null is returned for those rows
+   * instead ({@code RETURNS NULL ON NULL INPUT}).
+   *
+   * <p>DataFusion does not pass {@code STRICT} clause of {@code CREATE FUNCTION} to function
+   * factory, so strictness is declared by function class.
+   *
+   * @return true if function is strict, false by default
+   */
+  public boolean isStrict() {
+    return false;
+  }
+
+  /**
+   * Error policy of this function, overriding session error policy ({@code
+   * adhesive.error_policy}).
+   *
+   * @return error policy, or null to use session error policy (default)
+   */
+  public ErrorPolicy errorPolicy() {
+    return null;
+  }
+
+  /**
+   * Sets what to do when {@link #compute(Row)} throws for a row.
+   *
+   * <p>Set by caller when function is created, based on {@link #errorPolicy()} or session
+   * configuration.
+   *
+   * @param errorPolicy error policy name
+   */
+  public void setErrorPolicy(String errorPolicy) {
+    this.errorPolicy = ErrorPolicy.valueOf(errorPolicy.toUpperCase());
+  }
+
+  /**
+   * Imports return type exported by caller.
+   *
+   * <p>Used by entry points which can't create result vector from their name alone, like
+   * timestamps with time zone.
+   *
+   * @param addressReturnSchema address of exported return type
+   * @return return type field
+   */
+  static Field importReturnField(long addressReturnSchema) {
+    // schema will be released by import
+    return Data.importField(allocator, ArrowSchema.wrap(addressReturnSchema), null);
+  }
+
+  /**
+   * Computes result for all rows, compute entry points delegate to it.
+   *
+   * @return number of rows failed, with null written as their result ({@link ErrorPolicy#NULL}
+   *     and {@link ErrorPolicy#SKIP}). Failures are reported per invocation, so concurrent
+   *     invocations of the same instance do not mix them.
+   */
+  private <T extends FieldVector> long computeInternal(
       long addressInputSchema,
       long addressInputArray,
       long addressOutputSchema,
@@ -57,26 +128,43 @@ public abstract class Adhesive {
       logger.debug("java invoked ...");
 
       var vector = Data.importVector(allocator, inputArray, inputSchema, null);
-      var table = new Table(vector.getChildrenFromFields());
+      // row count is given by struct, function may have no arguments
+      var table = new Table(vector.getChildrenFromFields(), vector.getValueCount());
       var resultCount = table.getRowCount();
 
       logger.debug("java invoked ... input vector size: {}", resultCount);
 
-      resultVector.allocateNew((int) resultCount);
+      // works for both fixed and variable width vectors,
+      // variable width vectors will grow as values are set
+      resultVector.setInitialCapacity((int) resultCount);
+      resultVector.allocateNew();
 
+      var failures = 0L;
       var offset = 0;
       for (Iterator<Row> it = table.iterator(); it.hasNext(); offset++) {
-        addToResult.apply(offset, it.next());
+        var row = it.next();
+        try {
+          addToResult.apply(offset, row);
+        } catch (RuntimeException e) {
+          if (errorPolicy == ErrorPolicy.FAIL) {
+            throw e;
+          }
+
+          logger.debug("java invoked ... row {} failed", offset, e);
+          ValueWriter.setNull(resultVector, offset);
+          failures++;
+        }
       }
 
       resultVector.setValueCount(offset);
       Data.exportVector(allocator, resultVector, null, outputArray, outputSchema);
 
       logger.debug("java invoked ... DONE");
+      return failures;
     }
   }
 
-  public void computeBigInt(
+  public long computeBigInt(
       long addressInputSchema,
       long addressInputArray,
       long addressOutputSchema,
@@ -84,7 +172,7 @@ public abstract class Adhesive {
 
     var result = new BigIntVector("result", allocator);
 
-    this.computeInternal(
+    return this.computeInternal(
         addressInputSchema,
         addressInputArray,
         addressOutputSchema,
@@ -102,7 +190,7 @@ public abstract class Adhesive {
         });
   }
 
-  public void computeNonNullBigInt(
+  public long computeNonNullBigInt(
       long addressInputSchema,
       long addressInputArray,
       long addressOutputSchema,
@@ -110,7 +198,7 @@ public abstract class Adhesive {
 
     var result = new BigIntVector("result", allocator);
 
-    this.computeInternal(
+    return this.computeInternal(
         addressInputSchema,
         addressInputArray,
         addressOutputSchema,
@@ -123,7 +211,7 @@ public abstract class Adhesive {
         });
   }
 
-  public void computeInt(
+  public long computeInt(
       long addressInputSchema,
       long addressInputArray,
       long addressOutputSchema,
@@ -131,7 +219,7 @@ public abstract class Adhesive {
 
     var result = new IntVector("result", allocator);
 
-    this.computeInternal(
+    return this.computeInternal(
         addressInputSchema,
         addressInputArray,
         addressOutputSchema,
@@ -148,7 +236,7 @@ public abstract class Adhesive {
         });
   }
 
-  public void computeFloat(
+  public long computeFloat(
       long addressInputSchema,
       long addressInputArray,
       long addressOutputSchema,
@@ -156,7 +244,7 @@ public abstract class Adhesive {
 
     var result = new Float4Vector("result", allocator);
 
-    this.computeInternal(
+    return this.computeInternal(
         addressInputSchema,
         addressInputArray,
         addressOutputSchema,
@@ -173,7 +261,7 @@ public abstract class Adhesive {
         });
   }
 
-  public void computeDouble(
+  public long computeDouble(
       long addressInputSchema,
       long addressInputArray,
       long addressOutputSchema,
@@ -181,7 +269,7 @@ public abstract class Adhesive {
 
     var result = new Float8Vector("result", allocator);
 
-    this.computeInternal(
+    return this.computeInternal(
         addressInputSchema,
         addressInputArray,
         addressOutputSchema,
@@ -197,4 +285,506 @@ public abstract class Adhesive {
           return null;
         });
   }
+
+  public long computeSmallInt(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new SmallIntVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Short>compute(row);
+          if (r != null) {
+            result.set(index, r);
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeTinyInt(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new TinyIntVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Byte>compute(row);
+          if (r != null) {
+            result.set(index, r);
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeBoolean(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new BitVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Boolean>compute(row);
+          if (r != null) {
+            result.set(index, r ? 1 : 0);
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeVarChar(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new VarCharVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<String>compute(row);
+          if (r != null) {
+            result.setSafe(index, r.getBytes(StandardCharsets.UTF_8));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeLargeVarChar(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new LargeVarCharVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<String>compute(row);
+          if (r != null) {
+            result.setSafe(index, r.getBytes(StandardCharsets.UTF_8));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeViewVarChar(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new ViewVarCharVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<String>compute(row);
+          if (r != null) {
+            result.setSafe(index, r.getBytes(StandardCharsets.UTF_8));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeVarBinary(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new VarBinaryVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<byte[]>compute(row);
+          if (r != null) {
+            result.setSafe(index, r);
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeDateDay(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new DateDayVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<LocalDate>compute(row);
+          if (r != null) {
+            result.set(index, Math.toIntExact(r.toEpochDay()));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeDateMilli(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new DateMilliVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<LocalDate>compute(row);
+          if (r != null) {
+            result.set(index, Math.multiplyExact(r.toEpochDay(), 86_400_000L));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeTimeMicro(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new TimeMicroVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<LocalTime>compute(row);
+          if (r != null) {
+            result.set(index, r.toNanoOfDay() / 1_000L);
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeTimeNano(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new TimeNanoVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<LocalTime>compute(row);
+          if (r != null) {
+            result.set(index, r.toNanoOfDay());
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeIntervalYear(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new IntervalYearVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Period>compute(row);
+          if (r != null) {
+            result.set(index, Math.toIntExact(r.toTotalMonths()));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeIntervalDay(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new IntervalDayVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Duration>compute(row);
+          if (r != null) {
+            var days = r.toDays();
+            var millis = r.minusDays(days).toMillis();
+            result.set(index, Math.toIntExact(days), Math.toIntExact(millis));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  public long computeIntervalMonthDayNano(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    var result = new IntervalMonthDayNanoVector("result", allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Duration>compute(row);
+          if (r != null) {
+            // duration is exact amount of time,
+            // so it is kept in nanoseconds only
+            result.set(index, 0, 0, r.toNanos());
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  /**
+   * Timestamp entry point.
+   *
+   * <p>Time unit and (optional) time zone are taken from return type, time zone is retained on
+   * result vector.
+   */
+  public long computeTimeStamp(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray,
+      long addressReturnSchema) {
+
+    var field = importReturnField(addressReturnSchema);
+    var unit = ((ArrowType.Timestamp) field.getType()).getUnit();
+    var result = (TimeStampVector) field.createVector(allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<Instant>compute(row);
+          if (r != null) {
+            result.set(index, toEpoch(r, unit));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  static long toEpoch(Instant instant, TimeUnit unit) {
+    return switch (unit) {
+      case SECOND -> instant.getEpochSecond();
+      case MILLISECOND -> instant.toEpochMilli();
+      case MICROSECOND -> ChronoUnit.MICROS.between(Instant.EPOCH, instant);
+      case NANOSECOND -> ChronoUnit.NANOS.between(Instant.EPOCH, instant);
+    };
+  }
+
+  /**
+   * Decimal entry point.
+   *
+   * <p>Precision and scale are taken from return type. Values are rescaled to return type scale
+   * (rounding half up), values which do not fit return type precision are rejected.
+   */
+  public long computeDecimal(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray,
+      long addressReturnSchema) {
+
+    var field = importReturnField(addressReturnSchema);
+    var result = (DecimalVector) field.createVector(allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<BigDecimal>compute(row);
+          if (r != null) {
+            result.set(index, toDecimal(r, result.getPrecision(), result.getScale()));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  /**
+   * Decimal256 entry point.
+   *
+   * <p>Same rules as {@link #computeDecimal(long, long, long, long, long)} apply.
+   */
+  public long computeDecimal256(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray,
+      long addressReturnSchema) {
+
+    var field = importReturnField(addressReturnSchema);
+    var result = (Decimal256Vector) field.createVector(allocator);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          var r = this.<BigDecimal>compute(row);
+          if (r != null) {
+            result.set(index, toDecimal(r, result.getPrecision(), result.getScale()));
+          } else {
+            result.setNull(index);
+          }
+          return null;
+        });
+  }
+
+  static BigDecimal toDecimal(BigDecimal value, int precision, int scale) {
+    var rescaled = value.setScale(scale, RoundingMode.HALF_UP);
+    if (rescaled.precision() > precision) {
+      throw new ArithmeticException(
+          String.format(
+              "value %s does not fit into decimal(%d, %d)", value, precision, scale));
+    }
+
+    return rescaled;
+  }
+
+  /**
+   * Nested types entry point (list, struct and map).
+   *
+   * <p>Result vector is created from return type, values returned by {@link #compute(Row)} are
+   * written by {@link ValueWriter}.
+   */
+  public long computeNested(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray,
+      long addressReturnSchema) {
+
+    var field = importReturnField(addressReturnSchema);
+    var result = field.createVector(allocator);
+    var writer = ValueWriter.of(result);
+
+    return this.computeInternal(
+        addressInputSchema,
+        addressInputArray,
+        addressOutputSchema,
+        addressOutputArray,
+        result,
+        (index, row) -> {
+          writer.write(index, this.compute(row));
+          return null;
+        });
+  }
 }
@@ -0,0 +1,120 @@
+package com.github.milenkovicm.adhesive;
+
+import org.apache.arrow.c.ArrowArray;
+import org.apache.arrow.c.ArrowSchema;
+import org.apache.arrow.c.Data;
+import org.apache.arrow.vector.VarBinaryVector;
+import org.apache.arrow.vector.table.Row;
+import org.apache.arrow.vector.table.Table;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+/**
+ * Base class for aggregate functions.
+ *
+ * <p>New accumulator instance is created for each group (and partition), rows are added with
+ * {@link #update(Row)}. Partial results are moved between accumulators as opaque binary state,
+ * produced by {@link #state()} and consumed by {@link #merge(byte[])}.
+ *
+ * <p>Implementor should provide default constructor.
+ *
+ * @link "https://arrow.apache.org/docs/java/cdata.html"
+ */
+public abstract class AdhesiveAccumulator implements AdhesiveFunction {
+
+  static final Logger logger = LoggerFactory.getLogger(AdhesiveAccumulator.class);
+
+  /**
+   * Adds single row to accumulator
+   *
+   * @param row function arguments. Implementors should not access fields by name.
+   */
+  protected abstract void update(Row row);
+
+  /**
+   * Merges state of another accumulator into this one
+   *
+   * @param state state produced by {@link #state()}
+   */
+  protected abstract void merge(byte[] state);
+
+  /**
+   * Serializes accumulator state
+   *
+   * @return accumulator state, or null
+   */
+  protected abstract byte[] state();
+
+  /**
+   * Computes final result
+   *
+   * @return result, type should match function return type. See {@link ValueWriter} for expected
+   *     types.
+   * @param <T> result type
+   */
+  protected abstract <T> T evaluate();
+
+  public void updateBatch(long addressInputSchema, long addressInputArray) {
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null)) {
+
+      // row count is given by struct, function may have no arguments
+      var table = new Table(vector.getChildrenFromFields(), vector.getValueCount());
+      logger.debug("java update ... input vector size: {}", table.getRowCount());
+
+      for (var row : table) {
+        update(row);
+      }
+    }
+  }
+
+  public void mergeBatch(long addressInputSchema, long addressInputArray) {
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null)) {
+
+      // row count is given by struct, function may have no arguments
+      var table = new Table(vector.getChildrenFromFields(), vector.getValueCount());
+      logger.debug("java merge ... input vector size: {}", table.getRowCount());
+
+      for (var row : table) {
+        if (!row.isNull(0)) {
+          merge(row.getVarBinary(0));
+        }
+      }
+    }
+  }
+
+  public void exportState(long addressOutputSchema, long addressOutputArray) {
+    try (ArrowArray outputArray = ArrowArray.wrap(addressOutputArray);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var resultVector = new VarBinaryVector("state", Adhesive.allocator)) {
+
+      resultVector.allocateNew(1);
+      ValueWriter.of(resultVector).write(0, state());
+      resultVector.setValueCount(1);
+
+      // result buffers are retained by export
+      Data.exportVector(Adhesive.allocator, resultVector, null, outputArray, outputSchema);
+    }
+  }
+
+  public void exportResult(
+      long addressOutputSchema, long addressOutputArray, long addressReturnSchema) {
+    var field = Adhesive.importReturnField(addressReturnSchema);
+
+    try (ArrowArray outputArray = ArrowArray.wrap(addressOutputArray);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var resultVector = field.createVector(Adhesive.allocator)) {
+
+      resultVector.setInitialCapacity(1);
+      resultVector.allocateNew();
+      ValueWriter.of(resultVector).write(0, evaluate());
+      resultVector.setValueCount(1);
+
+      // result buffers are retained by export
+      Data.exportVector(Adhesive.allocator, resultVector, null, outputArray, outputSchema);
+    }
+  }
+}
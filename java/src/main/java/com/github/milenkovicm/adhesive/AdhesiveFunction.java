@@ -0,0 +1,121 @@
+package com.github.milenkovicm.adhesive;
+
+import java.lang.reflect.Method;
+import java.lang.reflect.Modifier;
+import java.util.List;
+import org.apache.arrow.c.ArrowArray;
+import org.apache.arrow.c.ArrowSchema;
+import org.apache.arrow.c.Data;
+import org.apache.arrow.vector.FieldVector;
+import org.apache.arrow.vector.types.pojo.Field;
+import org.apache.arrow.vector.types.pojo.Schema;
+
+/**
+ * Common interface of function base classes ({@link Adhesive}, {@link VectorizedAdhesive}, {@link
+ * AdhesiveAccumulator} and {@link AdhesiveWindow}).
+ */
+public interface AdhesiveFunction {
+
+  /**
+   * Exports expected input schema declared by function class, a field per function argument.
+   *
+   * <p>Schema is declared by static method of function class, {@code public static Schema
+   * inputSchema()}, so no function instance is created to read it. If declared, function argument
+   * types are checked against it when function is created, and function is rejected if they do not
+   * match. Field names and nullability are not checked.
+   *
+   * @param functionClass function class
+   * @param addressOutputSchema address of schema to export to
+   * @return true if input schema is declared, and it has been exported
+   */
+  static boolean exportInputSchema(Class<?> functionClass, long addressOutputSchema) {
+    Method method;
+    try {
+      method = functionClass.getMethod("inputSchema");
+    } catch (NoSuchMethodException e) {
+      return false;
+    }
+
+    if (!Modifier.isStatic(method.getModifiers()) || method.getReturnType() != Schema.class) {
+      throw new IllegalArgumentException(
+          "input schema should be declared as public static Schema inputSchema(), in "
+              + functionClass.getName());
+    }
+
+    Schema schema;
+    try {
+      schema = (Schema) method.invoke(null);
+    } catch (ReflectiveOperationException e) {
+      throw new IllegalStateException("can't read input schema of " + functionClass.getName(), e);
+    }
+
+    if (schema == null) {
+      return false;
+    }
+
+    try (ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema)) {
+      Data.exportSchema(Adhesive.allocator, schema, null, outputSchema);
+    }
+
+    return true;
+  }
+
+  /**
+   * Return type computed from argument types and literal arguments, like {@code parse(col,
+   * 'struct<a:int>')}.
+   *
+   * <p>Applies to row by row and vectorized functions, it is called when query is planned.
+   *
+   * @param arguments argument fields
+   * @param literals literal argument values, null if argument is not literal (or literal is null)
+   * @return return field, or null to use declared return type (default)
+   */
+  default Field returnField(List<Field> arguments, List<Object> literals) {
+    return null;
+  }
+
+  /**
+   * Checks if function computes its return field, so caller does not have to call {@link
+   * #exportReturnField(long, long, long)} for functions which do not.
+   *
+   * @return true if {@link #returnField(List, List)} is overridden
+   */
+  default boolean computesReturnField() {
+    try {
+      return getClass().getMethod("returnField", List.class, List.class).getDeclaringClass()
+          != AdhesiveFunction.class;
+    } catch (NoSuchMethodException e) {
+      return false;
+    }
+  }
+
+  /**
+   * Exports {@link #returnField(List, List)} to caller
+   *
+   * @param addressInputSchema address of arguments schema
+   * @param addressInputArray address of arguments, single row struct
+   * @param addressOutputSchema address of schema to export return field to
+   * @return true if return field is computed, and it has been exported
+   */
+  default boolean exportReturnField(
+      long addressInputSchema, long addressInputArray, long addressOutputSchema) {
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null)) {
+
+      var children = vector.getChildrenFromFields();
+      var arguments = children.stream().map(FieldVector::getField).toList();
+      // stream list allows null values
+      var literals = children.stream().map(v -> v.getObject(0)).toList();
+
+      var field = returnField(arguments, literals);
+      if (field == null) {
+        return false;
+      }
+
+      Data.exportField(Adhesive.allocator, field, null, outputSchema);
+      return true;
+    }
+  }
+}
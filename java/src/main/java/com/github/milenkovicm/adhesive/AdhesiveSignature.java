@@ -0,0 +1,58 @@
+package com.github.milenkovicm.adhesive;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+
+/**
+ * Function signature declared by function class.
+ *
+ * <p>Functions declaring signature can be created without argument and return types:
+ *
+ * <pre>{@code
+ * CREATE FUNCTION f() LANGUAGE CLASS AS 'com.example.Function'
+ * }</pre>
+ *
+ * <p>Types are arrow data type names, like {@code Int64}, {@code Utf8} or {@code Decimal128(10,
+ * 2)}. If function is created with argument or return types, they should match declared ones.
+ */
+@Retention(RetentionPolicy.RUNTIME)
+@Target(ElementType.TYPE)
+public @interface AdhesiveSignature {
+
+  /** How arguments are matched, arguments are coerced by caller to match signature. */
+  enum Kind {
+    /** arguments of {@link #arguments()} types */
+    EXACT,
+    /** one or more arguments of the same type, one of {@link #arguments()} */
+    VARIADIC,
+    /** one or more arguments of any type */
+    VARIADIC_ANY,
+    /** {@link #count()} arguments of the same type, one of {@link #arguments()} */
+    UNIFORM,
+    /** {@link #count()} arguments of the same numeric type */
+    NUMERIC
+  }
+
+  /**
+   * @return how arguments are matched, {@link Kind#EXACT} by default
+   */
+  Kind kind() default Kind.EXACT;
+
+  /**
+   * @return argument types, or valid argument types for {@link Kind#VARIADIC} and {@link
+   *     Kind#UNIFORM}
+   */
+  String[] arguments() default {};
+
+  /**
+   * @return number of arguments for {@link Kind#UNIFORM} and {@link Kind#NUMERIC}
+   */
+  int count() default 0;
+
+  /**
+   * @return return type
+   */
+  String returns();
+}
@@ -0,0 +1,79 @@
+package com.github.milenkovicm.adhesive;
+
+import org.apache.arrow.c.ArrowArray;
+import org.apache.arrow.c.ArrowArrayStream;
+import org.apache.arrow.c.ArrowSchema;
+import org.apache.arrow.c.Data;
+import org.apache.arrow.memory.BufferAllocator;
+import org.apache.arrow.vector.ipc.ArrowReader;
+import org.apache.arrow.vector.table.Row;
+import org.apache.arrow.vector.table.Table;
+import org.apache.arrow.vector.types.pojo.Schema;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+/**
+ * Base class for table functions, functions producing rows ({@code SELECT * FROM f('arg')}).
+ *
+ * <p>New instance is created for each function call. Produced rows are streamed back as record
+ * batches using arrow C stream interface, reader may be requested more than once if table is
+ * scanned multiple times.
+ *
+ * <p>Implementor should provide default constructor.
+ *
+ * @link "https://arrow.apache.org/docs/java/cdata.html"
+ */
+public abstract class AdhesiveTable {
+
+  static final Logger logger = LoggerFactory.getLogger(AdhesiveTable.class);
+
+  /**
+   * Schema of produced rows
+   *
+   * @param arguments function (literal) arguments. Implementors should not access fields by name.
+   * @return schema of record batches returned by reader
+   */
+  protected abstract Schema schema(Row arguments);
+
+  /**
+   * Creates reader producing rows
+   *
+   * @param arguments function (literal) arguments, valid only during this call. Values needed by
+   *     the reader should be copied.
+   * @param allocator allocator to be used to create record batches
+   * @return reader, it will be closed when stream is released
+   */
+  protected abstract ArrowReader read(Row arguments, BufferAllocator allocator);
+
+  public void exportSchema(
+      long addressInputSchema, long addressInputArray, long addressOutputSchema) {
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null)) {
+
+      var arguments = new Table(vector.getChildrenFromFields()).immutableRow();
+      arguments.setPosition(0);
+
+      Data.exportSchema(Adhesive.allocator, schema(arguments), null, outputSchema);
+    }
+  }
+
+  public void exportStream(
+      long addressInputSchema, long addressInputArray, long addressOutputStream) {
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        ArrowArrayStream outputStream = ArrowArrayStream.wrap(addressOutputStream);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null)) {
+
+      var arguments = new Table(vector.getChildrenFromFields()).immutableRow();
+      arguments.setPosition(0);
+
+      logger.debug("java table function ... opening stream");
+
+      // reader is owned by the stream
+      Data.exportArrayStream(
+          Adhesive.allocator, read(arguments, Adhesive.allocator), outputStream);
+    }
+  }
+}
@@ -0,0 +1,153 @@
+package com.github.milenkovicm.adhesive;
+
+import java.util.List;
+import org.apache.arrow.c.ArrowArray;
+import org.apache.arrow.c.ArrowSchema;
+import org.apache.arrow.c.Data;
+import org.apache.arrow.vector.FieldVector;
+import org.apache.arrow.vector.table.Table;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+/**
+ * Base class for window functions.
+ *
+ * <p>New instance is created for each partition. Functions which do not use window frame ({@link
+ * #usesWindowFrame()} returns false) are invoked once, with whole partition, using {@link
+ * #evaluateAll(Table)}. Otherwise function is invoked for every partition row, using {@link
+ * #evaluate(Table, int, int)} with row window frame.
+ *
+ * <p>Partition rows are ordered as requested by {@code ORDER BY} in window definition.
+ *
+ * <p>Implementor should provide default constructor.
+ *
+ * @link "https://arrow.apache.org/docs/java/cdata.html"
+ */
+public abstract class AdhesiveWindow implements AdhesiveFunction, AutoCloseable {
+
+  static final Logger logger = LoggerFactory.getLogger(AdhesiveWindow.class);
+
+  /** partition evaluated by row window frames */
+  private Table partition;
+
+  private FieldVector partitionVector;
+
+  /**
+   * Evaluates whole partition
+   *
+   * @param partition function arguments for all partition rows. Implementors should not access
+   *     fields by name.
+   * @return result for each partition row. See {@link ValueWriter} for expected types.
+   */
+  protected List<?> evaluateAll(Table partition) {
+    throw new UnsupportedOperationException("evaluateAll not implemented");
+  }
+
+  /**
+   * Evaluates single row window frame, used when {@link #usesWindowFrame()} is true
+   *
+   * @param partition function arguments for all partition rows
+   * @param start first row of the frame (inclusive)
+   * @param end last row of the frame (exclusive)
+   * @return result for the row. See {@link ValueWriter} for expected types.
+   */
+  protected Object evaluate(Table partition, int start, int end) {
+    throw new UnsupportedOperationException("evaluate not implemented");
+  }
+
+  /**
+   * @return true if function should be evaluated for each row window frame, false by default
+   */
+  public boolean usesWindowFrame() {
+    return false;
+  }
+
+  public void evaluateAllBatch(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray,
+      long addressReturnSchema) {
+    var field = Adhesive.importReturnField(addressReturnSchema);
+
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        ArrowArray outputArray = ArrowArray.wrap(addressOutputArray);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null);
+        var resultVector = field.createVector(Adhesive.allocator)) {
+
+      // row count is given by struct, function may have no arguments
+      var partition = new Table(vector.getChildrenFromFields(), vector.getValueCount());
+      logger.debug("java evaluate all ... partition size: {}", partition.getRowCount());
+
+      var result = evaluateAll(partition);
+      var writer = ValueWriter.of(resultVector);
+
+      resultVector.setInitialCapacity(result.size());
+      resultVector.allocateNew();
+      for (int i = 0; i < result.size(); i++) {
+        writer.write(i, result.get(i));
+      }
+      resultVector.setValueCount(result.size());
+
+      // result buffers are retained by export
+      Data.exportVector(Adhesive.allocator, resultVector, null, outputArray, outputSchema);
+    }
+  }
+
+  /**
+   * Sets partition used by {@link #evaluateBatch(long, long, long, long, long)}.
+   *
+   * <p>Partition is exported once, rather than for each row window frame. It is kept until next
+   * partition is set, or instance is closed.
+   */
+  public void setPartition(long addressInputSchema, long addressInputArray) {
+    close();
+
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema)) {
+      partitionVector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null);
+      partition =
+          new Table(partitionVector.getChildrenFromFields(), partitionVector.getValueCount());
+      logger.debug("java set partition ... partition size: {}", partition.getRowCount());
+    }
+  }
+
+  public void evaluateBatch(
+      long start,
+      long end,
+      long addressOutputSchema,
+      long addressOutputArray,
+      long addressReturnSchema) {
+    var field = Adhesive.importReturnField(addressReturnSchema);
+
+    if (partition == null) {
+      throw new IllegalStateException("partition is not set");
+    }
+
+    try (ArrowArray outputArray = ArrowArray.wrap(addressOutputArray);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var resultVector = field.createVector(Adhesive.allocator)) {
+
+      resultVector.setInitialCapacity(1);
+      resultVector.allocateNew();
+      ValueWriter.of(resultVector)
+          .write(0, evaluate(partition, Math.toIntExact(start), Math.toIntExact(end)));
+      resultVector.setValueCount(1);
+
+      // result buffers are retained by export
+      Data.exportVector(Adhesive.allocator, resultVector, null, outputArray, outputSchema);
+    }
+  }
+
+  /** Releases partition, if set */
+  @Override
+  public void close() {
+    if (partitionVector != null) {
+      partitionVector.close();
+      partitionVector = null;
+      partition = null;
+    }
+  }
+}
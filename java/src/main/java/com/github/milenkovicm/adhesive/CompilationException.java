@@ -0,0 +1,39 @@
+package com.github.milenkovicm.adhesive;
+
+import java.util.List;
+import javax.tools.Diagnostic;
+import javax.tools.JavaFileObject;
+
+/** Thrown when function source code can't be compiled, carries compiler diagnostics. */
+public class CompilationException extends InstantiationException {
+
+  /**
+   * Single compiler diagnostic
+   *
+   * @param severity diagnostic kind, like {@code ERROR} or {@code WARNING}
+   * @param line line number (starting from 1), or -1 if not known
+   * @param column column number (starting from 1), or -1 if not known
+   * @param message diagnostic message
+   */
+  public record CompilationDiagnostic(String severity, long line, long column, String message) {
+
+    static CompilationDiagnostic of(Diagnostic<? extends JavaFileObject> diagnostic) {
+      return new CompilationDiagnostic(
+          diagnostic.getKind().name(),
+          diagnostic.getLineNumber(),
+          diagnostic.getColumnNumber(),
+          diagnostic.getMessage(null));
+    }
+  }
+
+  private final List<CompilationDiagnostic> diagnostics;
+
+  public CompilationException(String message, List<CompilationDiagnostic> diagnostics) {
+    super(message);
+    this.diagnostics = List.copyOf(diagnostics);
+  }
+
+  public List<CompilationDiagnostic> getDiagnostics() {
+    return diagnostics;
+  }
+}
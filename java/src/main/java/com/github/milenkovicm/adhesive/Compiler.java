@@ -14,7 +14,7 @@ import org.slf4j.Logger;
 import org.slf4j.LoggerFactory;
 
 /**
- * Compiles given strings to java instances
+ * Compiles given strings to java classes
  *
  * @link "https://www.baeldung.com/java-string-compile-execute-code"
  */
@@ -31,8 +31,16 @@ public class Compiler {
   static final InMemoryFileManager manager =
       new InMemoryFileManager(compiler.getStandardFileManager(null, null, null));
 
-  public Adhesive compile(String qualifiedClassName, String sourceCode)
-      throws ClassNotFoundException, InstantiationException, IllegalAccessException {
+  /**
+   * Compiles source code
+   *
+   * @param qualifiedClassName class name
+   * @param sourceCode class source code
+   * @return compiled class
+   * @throws CompilationException if code can't be compiled
+   */
+  public Class<?> compile(String qualifiedClassName, String sourceCode)
+      throws ClassNotFoundException, InstantiationException {
     LOGGER.debug("Compiling class of: `{}`: \n ```java\n{}\n```", qualifiedClassName, sourceCode);
 
     DiagnosticCollector<JavaFileObject> diagnostics = new DiagnosticCollector<>();
@@ -44,17 +52,17 @@ public class Compiler {
         compiler.getTask(null, manager, diagnostics, null, null, sourceFiles);
 
     if (!task.call()) {
-      diagnostics.getDiagnostics().forEach(d -> LOGGER.error(String.valueOf(d)));
+      diagnostics.getDiagnostics().forEach(d -> LOGGER.debug(String.valueOf(d)));
 
-      // should we return null or throw exception here ?
-      // return null;
-      throw new InstantiationException("Can't compile function");
+      throw new CompilationException(
+          "Can't compile function",
+          diagnostics.getDiagnostics().stream()
+              .map(CompilationException.CompilationDiagnostic::of)
+              .toList());
     } else {
       ClassLoader classLoader = manager.getClassLoader(null);
-      Class<?> clazz = classLoader.loadClass(qualifiedClassName);
-      Adhesive instanceOfClass = (Adhesive) clazz.newInstance();
 
-      return instanceOfClass;
+      return classLoader.loadClass(qualifiedClassName);
     }
   }
 }
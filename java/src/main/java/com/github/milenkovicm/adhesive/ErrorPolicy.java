@@ -0,0 +1,11 @@
+package com.github.milenkovicm.adhesive;
+
+/** What to do when function fails to compute a row. */
+public enum ErrorPolicy {
+  /** Exception is propagated, failing the query */
+  FAIL,
+  /** Null is written for the failed row */
+  NULL,
+  /** Null is written for the failed row, and failure is counted */
+  SKIP
+}
@@ -0,0 +1,246 @@
+package com.github.milenkovicm.adhesive;
+
+import java.math.BigDecimal;
+import java.nio.charset.StandardCharsets;
+import java.time.Duration;
+import java.time.Instant;
+import java.time.LocalDate;
+import java.time.LocalTime;
+import java.time.Period;
+import java.util.List;
+import java.util.Map;
+import org.apache.arrow.vector.*;
+import org.apache.arrow.vector.complex.FixedSizeListVector;
+import org.apache.arrow.vector.complex.LargeListVector;
+import org.apache.arrow.vector.complex.ListVector;
+import org.apache.arrow.vector.complex.MapVector;
+import org.apache.arrow.vector.complex.StructVector;
+import org.apache.arrow.vector.types.pojo.ArrowType;
+
+/**
+ * Writes java objects to arrow vector, driven by vector type.
+ *
+ * <p>Used for return types which can't be written by fixed setter, like nested types. Expected
+ * java types:
+ *
+ * <ul>
+ *   <li>list - {@link Iterable}
+ *   <li>map - {@link Map}
+ *   <li>struct - {@link Map} (by field name), {@link Record} (by component name), {@link List} or
+ *       array (by field position)
+ *   <li>primitives - same types as corresponding {@link Adhesive} compute methods
+ * </ul>
+ *
+ * <p>All writes are "safe", vectors will grow as needed.
+ */
+@FunctionalInterface
+public interface ValueWriter {
+
+  /**
+   * Writes value at given index
+   *
+   * @param index vector index
+   * @param value value to write, or null
+   */
+  void write(int index, Object value);
+
+  /**
+   * Creates writer for given vector
+   *
+   * @param vector to write to
+   * @return writer
+   */
+  static ValueWriter of(FieldVector vector) {
+    ValueWriter writer =
+        switch (vector) {
+            // map vector is a list vector, so it should be matched first
+          case MapVector v -> mapWriter(v);
+          case ListVector v -> listWriter(v);
+          case LargeListVector v -> largeListWriter(v);
+          case FixedSizeListVector v -> fixedSizeListWriter(v);
+          case StructVector v -> structWriter(v);
+          case BigIntVector v -> (i, o) -> v.setSafe(i, ((Number) o).longValue());
+          case IntVector v -> (i, o) -> v.setSafe(i, ((Number) o).intValue());
+          case SmallIntVector v -> (i, o) -> v.setSafe(i, ((Number) o).shortValue());
+          case TinyIntVector v -> (i, o) -> v.setSafe(i, ((Number) o).byteValue());
+          case Float4Vector v -> (i, o) -> v.setSafe(i, ((Number) o).floatValue());
+          case Float8Vector v -> (i, o) -> v.setSafe(i, ((Number) o).doubleValue());
+          case BitVector v -> (i, o) -> v.setSafe(i, (Boolean) o ? 1 : 0);
+          case VarCharVector v ->
+              (i, o) -> v.setSafe(i, ((String) o).getBytes(StandardCharsets.UTF_8));
+          case LargeVarCharVector v ->
+              (i, o) -> v.setSafe(i, ((String) o).getBytes(StandardCharsets.UTF_8));
+          case ViewVarCharVector v ->
+              (i, o) -> v.setSafe(i, ((String) o).getBytes(StandardCharsets.UTF_8));
+          case VarBinaryVector v -> (i, o) -> v.setSafe(i, (byte[]) o);
+          case DateDayVector v ->
+              (i, o) -> v.setSafe(i, Math.toIntExact(((LocalDate) o).toEpochDay()));
+          case DateMilliVector v ->
+              (i, o) -> v.setSafe(i, Math.multiplyExact(((LocalDate) o).toEpochDay(), 86_400_000L));
+          case TimeMicroVector v -> (i, o) -> v.setSafe(i, ((LocalTime) o).toNanoOfDay() / 1_000L);
+          case TimeNanoVector v -> (i, o) -> v.setSafe(i, ((LocalTime) o).toNanoOfDay());
+          case IntervalYearVector v ->
+              (i, o) -> v.setSafe(i, Math.toIntExact(((Period) o).toTotalMonths()));
+          case IntervalDayVector v ->
+              (i, o) -> {
+                var duration = (Duration) o;
+                var days = duration.toDays();
+                var millis = duration.minusDays(days).toMillis();
+                v.setSafe(i, Math.toIntExact(days), Math.toIntExact(millis));
+              };
+            // duration is exact amount of time, so it is kept in nanoseconds only
+          case IntervalMonthDayNanoVector v ->
+              (i, o) -> v.setSafe(i, 0, 0, ((Duration) o).toNanos());
+          case TimeStampVector v -> {
+            var unit = ((ArrowType.Timestamp) v.getField().getType()).getUnit();
+            yield (i, o) -> v.setSafe(i, Adhesive.toEpoch((Instant) o, unit));
+          }
+          case DecimalVector v ->
+              (i, o) ->
+                  v.setSafe(i, Adhesive.toDecimal((BigDecimal) o, v.getPrecision(), v.getScale()));
+          case Decimal256Vector v ->
+              (i, o) ->
+                  v.setSafe(i, Adhesive.toDecimal((BigDecimal) o, v.getPrecision(), v.getScale()));
+          default ->
+              throw new UnsupportedOperationException(
+                  "type not supported by value writer: " + vector.getField());
+        };
+
+    return (index, value) -> {
+      if (value == null) {
+        setNull(vector, index);
+      } else {
+        writer.write(index, value);
+      }
+    };
+  }
+
+  private static ValueWriter listWriter(ListVector vector) {
+    var elements = ValueWriter.of(vector.getDataVector());
+
+    return (index, value) -> {
+      var start = vector.startNewValue(index);
+      var size = 0;
+      for (var element : (Iterable<?>) value) {
+        elements.write(start + size, element);
+        size++;
+      }
+      vector.endValue(index, size);
+    };
+  }
+
+  private static ValueWriter largeListWriter(LargeListVector vector) {
+    var elements = ValueWriter.of(vector.getDataVector());
+
+    return (index, value) -> {
+      var start = Math.toIntExact(vector.startNewValue(index));
+      var size = 0;
+      for (var element : (Iterable<?>) value) {
+        elements.write(start + size, element);
+        size++;
+      }
+      vector.endValue(index, size);
+    };
+  }
+
+  private static ValueWriter fixedSizeListWriter(FixedSizeListVector vector) {
+    var elements = ValueWriter.of(vector.getDataVector());
+
+    return (index, value) -> {
+      var start = vector.startNewValue(index);
+      var size = 0;
+      for (var element : (Iterable<?>) value) {
+        if (size == vector.getListSize()) {
+          throw new IllegalArgumentException(
+              "list has more than " + vector.getListSize() + " elements");
+        }
+        elements.write(start + size, element);
+        size++;
+      }
+      if (size != vector.getListSize()) {
+        throw new IllegalArgumentException(
+            "list has " + size + " elements, expected " + vector.getListSize());
+      }
+    };
+  }
+
+  private static ValueWriter mapWriter(MapVector vector) {
+    var entries = (StructVector) vector.getDataVector();
+    var keys = ValueWriter.of(entries.getChildrenFromFields().get(0));
+    var values = ValueWriter.of(entries.getChildrenFromFields().get(1));
+
+    return (index, value) -> {
+      var start = vector.startNewValue(index);
+      var size = 0;
+      for (var entry : ((Map<?, ?>) value).entrySet()) {
+        entries.setIndexDefined(start + size);
+        keys.write(start + size, entry.getKey());
+        values.write(start + size, entry.getValue());
+        size++;
+      }
+      vector.endValue(index, size);
+    };
+  }
+
+  private static ValueWriter structWriter(StructVector vector) {
+    var children = vector.getChildrenFromFields();
+    var names = children.stream().map(ValueVector::getName).toList();
+    var writers = children.stream().map(ValueWriter::of).toList();
+
+    return (index, value) -> {
+      vector.setIndexDefined(index);
+      for (int position = 0; position < writers.size(); position++) {
+        writers.get(position).write(index, structField(value, position, names.get(position)));
+      }
+    };
+  }
+
+  private static Object structField(Object value, int position, String name) {
+    return switch (value) {
+      case Map<?, ?> map -> map.get(name);
+      case Record r -> recordComponent(r, name);
+      case List<?> list -> list.get(position);
+      case Object[] array -> array[position];
+      default ->
+          throw new IllegalArgumentException(
+              "can't write " + value.getClass().getName() + " as a struct");
+    };
+  }
+
+  private static Object recordComponent(Record value, String name) {
+    for (var component : value.getClass().getRecordComponents()) {
+      if (component.getName().equals(name)) {
+        try {
+          var accessor = component.getAccessor();
+          accessor.setAccessible(true);
+          return accessor.invoke(value);
+        } catch (ReflectiveOperationException e) {
+          throw new IllegalStateException("can't read record component: " + name, e);
+        }
+      }
+    }
+    // missing components are written as nulls
+    return null;
+  }
+
+  /**
+   * Sets null at given index
+   *
+   * @param vector to write to
+   * @param index vector index
+   */
+  static void setNull(FieldVector vector, int index) {
+    switch (vector) {
+      case BaseFixedWidthVector v -> v.setNull(index);
+      case BaseVariableWidthVector v -> v.setNull(index);
+      case BaseVariableWidthViewVector v -> v.setNull(index);
+      case ListVector v -> v.setNull(index);
+      case LargeListVector v -> v.setNull(index);
+      case FixedSizeListVector v -> v.setNull(index);
+      case StructVector v -> v.setNull(index);
+      default ->
+          throw new UnsupportedOperationException(
+              "type not supported by value writer: " + vector.getField());
+    }
+  }
+}
@@ -0,0 +1,97 @@
+package com.github.milenkovicm.adhesive;
+
+import org.apache.arrow.c.ArrowArray;
+import org.apache.arrow.c.ArrowSchema;
+import org.apache.arrow.c.Data;
+import org.apache.arrow.memory.BufferAllocator;
+import org.apache.arrow.vector.FieldVector;
+import org.apache.arrow.vector.table.Table;
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+/**
+ * Base class for vectorized (columnar) functions.
+ *
+ * <p>Unlike {@link Adhesive}, which is invoked for every row, vectorized function is invoked once
+ * per batch with all arguments, and it returns whole result vector.
+ *
+ * <p>Implementor should provide default constructor.
+ *
+ * @link "https://arrow.apache.org/docs/java/cdata.html"
+ * @link "https://arrow.apache.org/docs/java/table.html"
+ */
+public abstract class VectorizedAdhesive implements AdhesiveFunction {
+
+  static final Logger logger = LoggerFactory.getLogger(VectorizedAdhesive.class);
+
+  /**
+   * Computation method
+   *
+   * <p>Implementors should not access fields by name. Argument vectors are owned by caller, they
+   * should not be closed or returned as result.
+   *
+   * @param arguments function arguments, a vector per argument
+   * @param allocator allocator to be used to create result vector
+   * @return result vector, with a value for each argument row. Its type should match function
+   *     return type.
+   */
+  protected abstract FieldVector compute(Table arguments, BufferAllocator allocator);
+
+  /**
+   * Strict functions are not invoked for rows with null arguments, null is returned for those rows
+   * instead ({@code RETURNS NULL ON NULL INPUT}).
+   *
+   * @return true if function is strict, false by default
+   * @see Adhesive#isStrict()
+   */
+  public boolean isStrict() {
+    return false;
+  }
+
+  /**
+   * Functions accepting constant arguments get literal arguments as {@link
+   * org.apache.arrow.vector.complex.RunEndEncodedVector} with a single run, rather than a vector
+   * with the same value repeated for every row.
+   *
+   * <p>Other functions get all arguments expanded to all rows.
+   *
+   * @return true if function accepts constant arguments, false by default
+   */
+  public boolean acceptsConstantArguments() {
+    return false;
+  }
+
+  /**
+   * Entry point, same as row by row {@link Adhesive} entry points.
+   *
+   * @return number of rows failed, always 0 as vectorized functions fail whole batch
+   */
+  public long computeVector(
+      long addressInputSchema,
+      long addressInputArray,
+      long addressOutputSchema,
+      long addressOutputArray) {
+
+    try (ArrowArray inputArray = ArrowArray.wrap(addressInputArray);
+        ArrowSchema inputSchema = ArrowSchema.wrap(addressInputSchema);
+        ArrowArray outputArray = ArrowArray.wrap(addressOutputArray);
+        ArrowSchema outputSchema = ArrowSchema.wrap(addressOutputSchema);
+        var vector = Data.importVector(Adhesive.allocator, inputArray, inputSchema, null)) {
+
+      logger.debug("java invoked ...");
+
+      // row count is given by struct, function may have no arguments
+      var table = new Table(vector.getChildrenFromFields(), vector.getValueCount());
+
+      logger.debug("java invoked ... input vector size: {}", table.getRowCount());
+
+      // result buffers are retained by export
+      try (var resultVector = compute(table, Adhesive.allocator)) {
+        Data.exportVector(Adhesive.allocator, resultVector, null, outputArray, outputSchema);
+      }
+
+      logger.debug("java invoked ... DONE");
+      return 0;
+    }
+  }
+}
@@ -0,0 +1,22 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.Adhesive;
+import com.github.milenkovicm.adhesive.AdhesiveSignature;
+import org.apache.arrow.vector.table.Row;
+
+/** Concatenates two (VARCHAR) arguments, declaring its own signature. */
+@AdhesiveSignature(
+    arguments = {"Utf8", "Utf8"},
+    returns = "Utf8")
+public class ConcatExample extends Adhesive {
+
+  @Override
+  public boolean isStrict() {
+    return true;
+  }
+
+  @Override
+  protected String compute(Row row) {
+    return row.getVarCharObj(0) + row.getVarCharObj(1);
+  }
+}
@@ -0,0 +1,26 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.AdhesiveWindow;
+import org.apache.arrow.vector.BigIntVector;
+import org.apache.arrow.vector.table.Table;
+
+/** Sums first (BIGINT) argument over row window frame, ignoring nulls. */
+public class FrameSumExample extends AdhesiveWindow {
+
+  @Override
+  public boolean usesWindowFrame() {
+    return true;
+  }
+
+  @Override
+  protected Long evaluate(Table partition, int start, int end) {
+    var values = (BigIntVector) partition.getVector(0);
+    var sum = 0L;
+    for (int i = start; i < end; i++) {
+      if (!values.isNull(i)) {
+        sum += values.get(i);
+      }
+    }
+    return sum;
+  }
+}
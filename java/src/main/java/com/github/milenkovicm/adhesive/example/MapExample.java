@@ -0,0 +1,20 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.Adhesive;
+import com.github.milenkovicm.adhesive.AdhesiveSignature;
+import java.util.Map;
+import java.util.TreeMap;
+import org.apache.arrow.vector.table.Row;
+
+/** Returns map with first two columns */
+@AdhesiveSignature(
+    arguments = {"Int64", "Int64"},
+    returns = "Map(Utf8, Int64)")
+public class MapExample extends Adhesive {
+
+  @Override
+  protected Map<String, Long> compute(Row row) {
+    // sorted, so entries are written in the same order
+    return new TreeMap<>(Map.of("a", row.getBigInt(0), "b", row.getBigInt(1)));
+  }
+}
@@ -0,0 +1,55 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.AdhesiveSignature;
+import com.github.milenkovicm.adhesive.VectorizedAdhesive;
+import org.apache.arrow.memory.BufferAllocator;
+import org.apache.arrow.vector.BigIntVector;
+import org.apache.arrow.vector.FieldVector;
+import org.apache.arrow.vector.complex.RunEndEncodedVector;
+import org.apache.arrow.vector.table.Table;
+
+/**
+ * Multiplies two (BIGINT) arguments. Literal arguments are passed as constants, rather than
+ * repeated for every row.
+ */
+@AdhesiveSignature(
+    arguments = {"Int64", "Int64"},
+    returns = "Int64")
+public class MultiplyExample extends VectorizedAdhesive {
+
+  @Override
+  public boolean acceptsConstantArguments() {
+    return true;
+  }
+
+  @Override
+  protected FieldVector compute(Table arguments, BufferAllocator allocator) {
+    var a = arguments.getVector(0);
+    var b = arguments.getVector(1);
+    var rowCount = (int) arguments.getRowCount();
+
+    var result = new BigIntVector("result", allocator);
+    result.allocateNew(rowCount);
+
+    for (int i = 0; i < rowCount; i++) {
+      var left = value(a, i);
+      var right = value(b, i);
+      if (left == null || right == null) {
+        result.setNull(i);
+      } else {
+        result.set(i, left * right);
+      }
+    }
+
+    result.setValueCount(rowCount);
+    return result;
+  }
+
+  /** value of given row, constant vector has a single value for all rows */
+  private static Long value(FieldVector vector, int index) {
+    if (vector instanceof RunEndEncodedVector constant) {
+      return ((BigIntVector) constant.getValuesVector()).getObject(0);
+    }
+    return ((BigIntVector) vector).getObject(index);
+  }
+}
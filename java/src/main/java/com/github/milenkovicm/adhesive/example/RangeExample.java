@@ -0,0 +1,65 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.AdhesiveTable;
+import java.util.List;
+import org.apache.arrow.memory.BufferAllocator;
+import org.apache.arrow.vector.BigIntVector;
+import org.apache.arrow.vector.ipc.ArrowReader;
+import org.apache.arrow.vector.table.Row;
+import org.apache.arrow.vector.types.pojo.ArrowType;
+import org.apache.arrow.vector.types.pojo.Field;
+import org.apache.arrow.vector.types.pojo.Schema;
+
+/** Produces rows with values from zero to first (BIGINT) argument (exclusive), in small batches. */
+public class RangeExample extends AdhesiveTable {
+  static final int BATCH_SIZE = 4;
+  static final Schema SCHEMA =
+      new Schema(List.of(Field.notNullable("value", new ArrowType.Int(64, true))));
+
+  @Override
+  protected Schema schema(Row arguments) {
+    return SCHEMA;
+  }
+
+  @Override
+  protected ArrowReader read(Row arguments, BufferAllocator allocator) {
+    var end = arguments.getBigInt(0);
+
+    return new ArrowReader(allocator) {
+      private long next = 0;
+
+      @Override
+      public boolean loadNextBatch() {
+        if (next >= end) {
+          return false;
+        }
+
+        var root = getVectorSchemaRoot();
+        var vector = (BigIntVector) root.getVector(0);
+        var count = (int) Math.min(BATCH_SIZE, end - next);
+
+        vector.allocateNew(count);
+        for (int i = 0; i < count; i++) {
+          vector.set(i, next + i);
+        }
+        root.setRowCount(count);
+        next += count;
+
+        return true;
+      }
+
+      @Override
+      public long bytesRead() {
+        return 0;
+      }
+
+      @Override
+      protected void closeReadSource() {}
+
+      @Override
+      protected Schema readSchema() {
+        return SCHEMA;
+      }
+    };
+  }
+}
@@ -0,0 +1,31 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.Adhesive;
+import java.util.List;
+import org.apache.arrow.vector.table.Row;
+import org.apache.arrow.vector.types.pojo.ArrowType;
+import org.apache.arrow.vector.types.pojo.Field;
+import org.apache.arrow.vector.types.pojo.Schema;
+
+/** Repeats second (VARCHAR) argument number of times given by first (BIGINT) argument. */
+public class RepeatExample extends Adhesive {
+  static final Schema INPUT_SCHEMA =
+      new Schema(
+          List.of(
+              Field.nullable("count", new ArrowType.Int(64, true)),
+              Field.nullable("value", ArrowType.Utf8.INSTANCE)));
+
+  public static Schema inputSchema() {
+    return INPUT_SCHEMA;
+  }
+
+  @Override
+  public boolean isStrict() {
+    return true;
+  }
+
+  @Override
+  protected String compute(Row row) {
+    return row.getVarCharObj(1).repeat((int) row.getBigInt(0));
+  }
+}
@@ -0,0 +1,31 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.Adhesive;
+import com.github.milenkovicm.adhesive.AdhesiveSignature;
+import java.math.BigDecimal;
+import java.util.List;
+import org.apache.arrow.vector.table.Row;
+import org.apache.arrow.vector.types.pojo.ArrowType;
+import org.apache.arrow.vector.types.pojo.Field;
+
+/**
+ * Converts first (BIGINT) argument to decimal, with scale given by second (literal BIGINT)
+ * argument.
+ */
+@AdhesiveSignature(
+    arguments = {"Int64", "Int64"},
+    returns = "Decimal128(20, 0)")
+public class ScaleExample extends Adhesive {
+
+  @Override
+  public Field returnField(List<Field> arguments, List<Object> literals) {
+    var scale = literals.get(1) instanceof Long s ? s.intValue() : 0;
+    return Field.nullable("result", new ArrowType.Decimal(20, scale, 128));
+  }
+
+  @Override
+  protected BigDecimal compute(Row row) {
+    // value is rescaled to return type scale
+    return BigDecimal.valueOf(row.getBigInt(0));
+  }
+}
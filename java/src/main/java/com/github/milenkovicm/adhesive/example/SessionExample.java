@@ -0,0 +1,31 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.AdhesiveWindow;
+import java.util.ArrayList;
+import java.util.List;
+import org.apache.arrow.vector.table.Table;
+
+/**
+ * Assigns session number to each partition row. First argument (BIGINT) is event time, new session
+ * starts when gap from previous event is greater than second argument (BIGINT).
+ */
+public class SessionExample extends AdhesiveWindow {
+
+  @Override
+  protected List<?> evaluateAll(Table partition) {
+    var result = new ArrayList<Long>();
+    var session = 0L;
+    Long previous = null;
+
+    for (var row : partition) {
+      var current = row.getBigInt(0);
+      if (previous != null && current - previous > row.getBigInt(1)) {
+        session++;
+      }
+      previous = current;
+      result.add(session);
+    }
+
+    return result;
+  }
+}
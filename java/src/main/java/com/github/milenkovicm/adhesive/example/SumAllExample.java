@@ -0,0 +1,40 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.AdhesiveSignature;
+import com.github.milenkovicm.adhesive.VectorizedAdhesive;
+import org.apache.arrow.memory.BufferAllocator;
+import org.apache.arrow.vector.BigIntVector;
+import org.apache.arrow.vector.FieldVector;
+import org.apache.arrow.vector.table.Table;
+
+/** Sums any number of (BIGINT) arguments, integer arguments are coerced to BIGINT. */
+@AdhesiveSignature(
+    kind = AdhesiveSignature.Kind.VARIADIC,
+    arguments = {"Int64"},
+    returns = "Int64")
+public class SumAllExample extends VectorizedAdhesive {
+
+  @Override
+  public boolean isStrict() {
+    return true;
+  }
+
+  @Override
+  protected FieldVector compute(Table arguments, BufferAllocator allocator) {
+    var rowCount = (int) arguments.getRowCount();
+
+    var result = new BigIntVector("result", allocator);
+    result.allocateNew(rowCount);
+
+    for (int i = 0; i < rowCount; i++) {
+      long sum = 0;
+      for (int c = 0; c < arguments.getVectorCount(); c++) {
+        sum += ((BigIntVector) arguments.getVector(c)).get(i);
+      }
+      result.set(i, sum);
+    }
+
+    result.setValueCount(rowCount);
+    return result;
+  }
+}
@@ -0,0 +1,36 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.AdhesiveAccumulator;
+import java.nio.ByteBuffer;
+import org.apache.arrow.vector.table.Row;
+
+/** Sums first (BIGINT) argument, ignoring nulls. Returns null if there are no values. */
+public class SumExample extends AdhesiveAccumulator {
+  private Long sum = null;
+
+  @Override
+  protected void update(Row row) {
+    if (!row.isNull(0)) {
+      add(row.getBigInt(0));
+    }
+  }
+
+  @Override
+  protected void merge(byte[] state) {
+    add(ByteBuffer.wrap(state).getLong());
+  }
+
+  @Override
+  protected byte[] state() {
+    return sum == null ? null : ByteBuffer.allocate(Long.BYTES).putLong(sum).array();
+  }
+
+  @Override
+  protected Long evaluate() {
+    return sum;
+  }
+
+  private void add(long value) {
+    sum = sum == null ? value : sum + value;
+  }
+}
@@ -0,0 +1,32 @@
+package com.github.milenkovicm.adhesive.example;
+
+import com.github.milenkovicm.adhesive.VectorizedAdhesive;
+import org.apache.arrow.memory.BufferAllocator;
+import org.apache.arrow.vector.BigIntVector;
+import org.apache.arrow.vector.FieldVector;
+import org.apache.arrow.vector.table.Table;
+
+/** Vectorized version of {@link BasicExample} */
+public class VectorizedExample extends VectorizedAdhesive {
+
+  @Override
+  protected FieldVector compute(Table arguments, BufferAllocator allocator) {
+    var a = (BigIntVector) arguments.getVector(0);
+    var b = (BigIntVector) arguments.getVector(1);
+    var rowCount = (int) arguments.getRowCount();
+
+    var result = new BigIntVector("result", allocator);
+    result.allocateNew(rowCount);
+
+    for (int i = 0; i < rowCount; i++) {
+      if (a.isNull(i) || b.isNull(i)) {
+        result.setNull(i);
+      } else {
+        result.set(i, a.get(i) + b.get(i));
+      }
+    }
+
+    result.setValueCount(rowCount);
+    return result;
+  }
+}
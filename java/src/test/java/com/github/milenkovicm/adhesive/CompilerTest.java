@@ -24,7 +24,8 @@ public class CompilerTest {
 
     var compiler = new Compiler();
 
-    var instance = compiler.compile("com.github.milenkovicm.newclass.NewClass", sourceCode);
+    var clazz = compiler.compile("com.github.milenkovicm.newclass.NewClass", sourceCode);
+    var instance = (Adhesive) clazz.getDeclaredConstructor().newInstance();
 
     assertNotNull(instance);
     instance.compute(null);
@@ -43,4 +44,21 @@ public class CompilerTest {
 
     assertEquals("Can't compile function", exception.getMessage());
   }
+
+  @Test()
+  public void compileDiagnosticsTest() throws Exception {
+
+    var compiler = new Compiler();
+    var brokenSourceCode = sourceCode.replace("return null;", "return null");
+
+    var exception =
+        assertThrows(
+            CompilationException.class,
+            () -> compiler.compile("com.github.milenkovicm.newclass.NewClass", brokenSourceCode));
+
+    var diagnostic = exception.getDiagnostics().get(0);
+    assertEquals("ERROR", diagnostic.severity());
+    assertEquals(7, diagnostic.line());
+    assertTrue(diagnostic.message().contains("';' expected"));
+  }
 }
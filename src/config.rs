@@ -0,0 +1,281 @@
+use std::path::{Path, PathBuf};
+
+use jni::{InitArgs, InitArgsBuilder, JNIVersion};
+
+use crate::{jvm::Result, JvmFunctionError};
+
+/// option required by arrow memory management
+const ADD_OPENS_OPTION: &str = "--add-opens=java.base/java.nio=ALL-UNNAMED";
+
+/// Garbage collector to be used by JVM
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GarbageCollector {
+    Serial,
+    Parallel,
+    G1,
+    Z,
+    Shenandoah,
+}
+
+impl GarbageCollector {
+    fn option(&self) -> &'static str {
+        match self {
+            GarbageCollector::Serial => "-XX:+UseSerialGC",
+            GarbageCollector::Parallel => "-XX:+UseParallelGC",
+            GarbageCollector::G1 => "-XX:+UseG1GC",
+            GarbageCollector::Z => "-XX:+UseZGC",
+            GarbageCollector::Shenandoah => "-XX:+UseShenandoahGC",
+        }
+    }
+}
+
+/// JVM configuration
+///
+/// ```ignore
+/// let config = JvmConfig::new()
+///     .with_class_path("adhesive-1.0-jar-with-dependencies.jar")
+///     .with_max_heap("2g")
+///     .with_gc(GarbageCollector::G1)
+///     .with_property("user.timezone", "UTC");
+///
+/// let factory = JvmFunctionFactory::new_with_config(config)?;
+/// ```
+///
+/// Paths and sizes are validated when factory is created,
+/// before JVM is started.
+#[derive(Debug, Clone)]
+pub struct JvmConfig {
+    jni_version: JNIVersion,
+    min_heap: Option<String>,
+    max_heap: Option<String>,
+    gc: Option<GarbageCollector>,
+    properties: Vec<(String, String)>,
+    java_agents: Vec<(PathBuf, Option<String>)>,
+    class_path: Vec<PathBuf>,
+    options: Vec<String>,
+}
+
+impl Default for JvmConfig {
+    fn default() -> Self {
+        Self {
+            jni_version: JNIVersion::V8,
+            min_heap: None,
+            max_heap: None,
+            gc: None,
+            properties: vec![],
+            java_agents: vec![],
+            class_path: vec![],
+            options: vec![],
+        }
+    }
+}
+
+impl JvmConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// JNI version, `V8` by default
+    pub fn with_jni_version(mut self, jni_version: JNIVersion) -> Self {
+        self.jni_version = jni_version;
+        self
+    }
+
+    /// Initial heap size, like `512m` (`-Xms`)
+    pub fn with_min_heap(mut self, size: impl Into<String>) -> Self {
+        self.min_heap = Some(size.into());
+        self
+    }
+
+    /// Max heap size, like `2g` (`-Xmx`)
+    pub fn with_max_heap(mut self, size: impl Into<String>) -> Self {
+        self.max_heap = Some(size.into());
+        self
+    }
+
+    pub fn with_gc(mut self, gc: GarbageCollector) -> Self {
+        self.gc = Some(gc);
+        self
+    }
+
+    /// System property (`-Dkey=value`)
+    pub fn with_property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.push((key.into(), value.into()));
+        self
+    }
+
+    /// Java agent jar, with optional agent options (`-javaagent:jar=options`)
+    pub fn with_java_agent(mut self, jar: impl AsRef<Path>, options: Option<&str>) -> Self {
+        self.java_agents
+            .push((jar.as_ref().to_path_buf(), options.map(|o| o.to_string())));
+        self
+    }
+
+    /// Class path entry, jar or directory
+    pub fn with_class_path(mut self, path: impl AsRef<Path>) -> Self {
+        self.class_path.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Any other JVM option, passed as is
+    pub fn with_option(mut self, option: impl Into<String>) -> Self {
+        self.options.push(option.into());
+        self
+    }
+
+    pub(crate) fn jni_version(&self) -> JNIVersion {
+        self.jni_version
+    }
+
+    /// Validates configuration and creates JVM options
+    pub(crate) fn jvm_options(&self) -> Result<Vec<String>> {
+        let mut options = vec![ADD_OPENS_OPTION.to_string()];
+
+        if let Some(size) = &self.min_heap {
+            options.push(format!("-Xms{}", validate_size(size)?));
+        }
+        if let Some(size) = &self.max_heap {
+            options.push(format!("-Xmx{}", validate_size(size)?));
+        }
+        if let Some(gc) = &self.gc {
+            options.push(gc.option().to_string());
+        }
+
+        for (key, value) in &self.properties {
+            if key.is_empty() || key.contains('=') {
+                Err(config_error(format!(
+                    "invalid system property name: [{key}]"
+                )))?
+            }
+            if key == "java.class.path" {
+                Err(config_error(
+                    "class path should be set using class path entries",
+                ))?
+            }
+            options.push(format!("-D{key}={value}"));
+        }
+
+        for (jar, agent_options) in &self.java_agents {
+            let jar = validate_path(jar, "java agent")?;
+            match agent_options {
+                Some(agent_options) => options.push(format!("-javaagent:{jar}={agent_options}")),
+                None => options.push(format!("-javaagent:{jar}")),
+            }
+        }
+
+        if !self.class_path.is_empty() {
+            for path in &self.class_path {
+                validate_path(path, "class path entry")?;
+            }
+            let class_path = std::env::join_paths(&self.class_path)
+                .map_err(|e| config_error(format!("invalid class path: {e}")))?;
+            options.push(format!(
+                "-Djava.class.path={}",
+                class_path.to_string_lossy()
+            ));
+        }
+
+        options.extend(self.options.iter().cloned());
+
+        Ok(options)
+    }
+
+    /// Creates JVM arguments from (validated) options
+    pub(crate) fn init_args(jni_version: JNIVersion, options: &[String]) -> Result<InitArgs<'_>> {
+        let jvm_args = options
+            .iter()
+            .fold(InitArgsBuilder::new().version(jni_version), |b, o| {
+                b.option(o.as_str())
+            })
+            .build()?;
+
+        Ok(jvm_args)
+    }
+}
+
+fn config_error(message: impl Into<String>) -> JvmFunctionError {
+    JvmFunctionError::JvmConfigError(message.into())
+}
+
+/// heap size, number with optional `k`, `m` or `g` unit
+fn validate_size(size: &str) -> Result<&str> {
+    let digits = size.trim_end_matches(['k', 'K', 'm', 'M', 'g', 'G']);
+    let valid = !digits.is_empty()
+        && size.len() - digits.len() <= 1
+        && digits.chars().all(|c| c.is_ascii_digit());
+
+    if valid {
+        Ok(size)
+    } else {
+        Err(config_error(format!(
+            "invalid heap size: [{size}], expected number with optional k, m or g unit"
+        )))
+    }
+}
+
+fn validate_path(path: &Path, description: &str) -> Result<String> {
+    if path.exists() {
+        Ok(path.to_string_lossy().to_string())
+    } else {
+        Err(config_error(format!(
+            "{description} does not exist: [{}]",
+            path.display()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GarbageCollector, JvmConfig};
+
+    #[test]
+    fn should_create_jvm_options() -> crate::jvm::Result<()> {
+        let config = JvmConfig::new()
+            .with_class_path("Cargo.toml")
+            .with_class_path("src")
+            .with_min_heap("256m")
+            .with_max_heap("2G")
+            .with_gc(GarbageCollector::G1)
+            .with_property("user.timezone", "UTC")
+            .with_option("-Xss4m");
+
+        let options = config.jvm_options()?;
+
+        assert_eq!(
+            vec![
+                "--add-opens=java.base/java.nio=ALL-UNNAMED",
+                "-Xms256m",
+                "-Xmx2G",
+                "-XX:+UseG1GC",
+                "-Duser.timezone=UTC",
+                "-Djava.class.path=Cargo.toml:src",
+                "-Xss4m",
+            ],
+            options
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_missing_class_path_entry() {
+        let config = JvmConfig::new().with_class_path("does-not-exist.jar");
+
+        let error = config.jvm_options().unwrap_err().to_string();
+        assert!(error.contains("does-not-exist.jar"), "{error}");
+    }
+
+    #[test]
+    fn should_reject_missing_java_agent() {
+        let config = JvmConfig::new().with_java_agent("agent.jar", Some("verbose"));
+
+        assert!(config.jvm_options().is_err());
+    }
+
+    #[test]
+    fn should_reject_invalid_heap_size() {
+        assert!(JvmConfig::new().with_max_heap("2gb").jvm_options().is_err());
+        assert!(JvmConfig::new().with_max_heap("g").jvm_options().is_err());
+        assert!(JvmConfig::new().with_min_heap("").jvm_options().is_err());
+    }
+}
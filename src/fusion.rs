@@ -1,20 +1,44 @@
-use std::sync::Arc;
+use std::{
+    collections::HashMap,
+    ops::Range,
+    sync::{Arc, Mutex},
+};
 
-use datafusion::arrow::{array::ArrayRef, datatypes::DataType};
-use datafusion::execution::context::SessionState;
+use datafusion::arrow::{
+    array::{
+        new_null_array, Array, ArrayData, ArrayRef, BooleanArray, Int32Array, RecordBatchReader,
+        RunArray, UInt32Array,
+    },
+    buffer::NullBuffer,
+    compute::{cast, filter, take},
+    datatypes::{DataType, Field, IntervalUnit, SchemaRef, TimeUnit},
+};
+use datafusion::catalog::{streaming::StreamingTable, TableFunctionImpl, TableProvider};
+use datafusion::common::plan_err;
+use datafusion::execution::{context::SessionState, SendableRecordBatchStream, TaskContext};
 use datafusion::logical_expr::Expr;
+use datafusion::logical_expr::{
+    function::{AccumulatorArgs, PartitionEvaluatorArgs, StateFieldsArgs, WindowUDFFieldArgs},
+    utils::format_state_name,
+    Accumulator, AggregateUDF, AggregateUDFImpl, PartitionEvaluator, WindowUDF, WindowUDFImpl,
+};
+use datafusion::physical_plan::{stream::RecordBatchReceiverStream, streaming::PartitionStream};
 use datafusion::scalar::ScalarValue;
 use datafusion::{
     common::exec_err,
     execution::context::{FunctionFactory, RegisterFunction},
     logical_expr::{
-        ColumnarValue, CreateFunction, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+        ColumnarValue, CreateFunction, ReturnInfo, ReturnTypeArgs, ScalarFunctionArgs, ScalarUDF,
+        ScalarUDFImpl, Signature, TypeSignature, Volatility,
     },
 };
 
 use crate::{
-    jvm::{JvmFunction, JvmFunctionFactory},
-    JvmFunctionError,
+    jvm::{
+        signature_accepts, JvmAccumulator, JvmClass, JvmFunction, JvmFunctionFactory,
+        JvmFunctionKind, JvmMethod, JvmPartitionEvaluator, JvmTableSource,
+    },
+    AdhesiveOptions, JvmFunctionError,
 };
 use datafusion::error::{DataFusionError, Result};
 
@@ -22,28 +46,25 @@ use datafusion::error::{DataFusionError, Result};
 impl FunctionFactory for JvmFunctionFactory {
     async fn create(
         &self,
-        _state: &SessionState,
+        state: &SessionState,
         statement: CreateFunction,
     ) -> Result<RegisterFunction> {
-        let return_type = statement.return_type.expect("return type expected");
-        let method_name = Self::return_type_to_method_name(&return_type)?;
-
         let language = statement
             .params
             .language
             .map(|i| i.value.to_lowercase())
             .unwrap_or("java".to_string());
 
-        let (jvm_function, function_definition) =
+        let (jvm_class, function_definition) =
             match (&statement.params.function_body, language.as_str()) {
                 (Some(Expr::Literal(ScalarValue::Utf8(Some(java_code)))), "java") => (
-                    self.compile_create_function(java_code, &method_name)?,
+                    self.compile_class(java_code)?,
                     FunctionDefinition::Java {
                         class_definition: java_code.to_owned(),
                     },
                 ),
                 (Some(Expr::Literal(ScalarValue::Utf8(Some(class_name)))), "class") => (
-                    self.create_function(class_name, &method_name)?,
+                    self.load_class(class_name)?,
                     FunctionDefinition::Fqn {
                         fqn: class_name.to_owned(),
                     },
@@ -57,47 +78,228 @@ impl FunctionFactory for JvmFunctionFactory {
                 _ => exec_err!("class name or class definition should be provided")?,
             };
 
-        let argument_types = statement
-            .args
-            .map(|args| {
-                args.into_iter()
-                    .map(|a| a.data_type)
-                    .collect::<Vec<DataType>>()
-            })
+        let argument_types = statement.args.map(|args| {
+            args.into_iter()
+                .map(|a| a.data_type)
+                .collect::<Vec<DataType>>()
+        });
+
+        // types not provided by statement are declared by function class
+        let (type_signature, return_type) =
+            self.resolve_signature(&jvm_class, argument_types, statement.return_type)?;
+
+        // functions are volatile, unless declared otherwise
+        let volatility = statement.params.behavior.unwrap_or(Volatility::Volatile);
+
+        let method = match jvm_class.kind() {
+            JvmFunctionKind::Scalar => Self::return_type_to_method(&return_type)?,
+            // vectorized functions create result vector on their own
+            JvmFunctionKind::VectorizedScalar => JvmMethod::new("computeVector"),
+            // accumulators are created by datafusion, when needed
+            JvmFunctionKind::Aggregate => {
+                let f = JvmAggregateWrapper {
+                    name: statement.name,
+                    signature: Signature::new(type_signature, volatility),
+                    function_definition,
+                    return_type,
+                    class: jvm_class,
+                };
+
+                return Ok(RegisterFunction::Aggregate(Arc::new(AggregateUDF::from(f))));
+            }
+            // partition evaluators are created by datafusion, when needed
+            JvmFunctionKind::Window => {
+                let f = JvmWindowWrapper {
+                    name: statement.name,
+                    signature: Signature::new(type_signature, volatility),
+                    function_definition,
+                    return_type,
+                    class: jvm_class,
+                };
+
+                return Ok(RegisterFunction::Window(Arc::new(WindowUDF::from(f))));
+            }
+            JvmFunctionKind::Table => exec_err!(
+                "table function [{}] can't be created with CREATE FUNCTION, use JvmFunctionFactory::create_table_function",
+                statement.name
+            )?,
+        };
+        let error_policy = state
+            .config()
+            .options()
+            .extensions
+            .get::<AdhesiveOptions>()
+            .map(|o| o.error_policy)
             .unwrap_or_default();
 
-        let f = JvmFunctionWrapper {
-            name: statement.name,
-            argument_types: argument_types.clone(),
-            signature: Signature::exact(argument_types, Volatility::Volatile),
-            function_definition,
+        // function with the same name is overloaded, unless it is replaced,
+        // existing overload can't be created again without replacing function
+        let overloads = match state.scalar_functions().get(&statement.name) {
+            Some(existing) if !statement.or_replace => existing
+                .inner()
+                .as_any()
+                .downcast_ref::<JvmFunctionWrapper>()
+                .map(|f| f.overloads.clone())
+                .unwrap_or_default(),
+            _ => vec![],
+        };
+
+        if overloads.iter().any(|o| o.type_signature == type_signature) {
+            exec_err!(
+                "function [{}] with arguments ({}) already exists, use CREATE OR REPLACE FUNCTION to replace it",
+                statement.name,
+                type_signature.to_string_repr().join(", ")
+            )?
+        }
+
+        let jvm_function = self.create_function(&jvm_class, &method, error_policy)?;
+        self.track_failures(
+            &statement.name,
+            &type_signature,
+            &jvm_function,
+            !overloads.is_empty(),
+        );
+
+        let overload = JvmOverload {
+            type_signature,
+            volatility,
             return_type,
+            function_definition,
             inner: jvm_function,
+            methods: Mutex::default(),
+            return_types: Mutex::default(),
         };
 
+        let f = JvmFunctionWrapper::new(statement.name, overloads, overload);
+
         Ok(RegisterFunction::Scalar(Arc::new(ScalarUDF::from(f))))
     }
 }
 
 impl JvmFunctionFactory {
-    fn return_type_to_method_name(return_type: &DataType) -> Result<String> {
+    /// Creates table function from java class extending `AdhesiveTable`.
+    ///
+    /// `FunctionFactory` can't create table functions,
+    /// they have to be registered with context:
+    ///
+    /// ```ignore
+    /// let factory = Arc::new(JvmFunctionFactory::new_with_jar(JAR_PATH)?);
+    /// ctx.register_udtf("f_range", factory.create_table_function("com.example.Range")?);
+    /// ```
+    pub fn create_table_function(&self, class_name: &str) -> Result<Arc<dyn TableFunctionImpl>> {
+        let class = self.load_class(class_name)?;
+
+        if class.kind() != JvmFunctionKind::Table {
+            exec_err!("class [{class_name}] is not a table function")?
+        }
+
+        Ok(Arc::new(JvmTableFunction { class }))
+    }
+
+    fn return_type_to_method(return_type: &DataType) -> Result<JvmMethod> {
         let method_name = match return_type {
             DataType::Int64 => "computeBigInt",
-            _ => exec_err!("type not supported (to be added)")?,
+            DataType::Int32 => "computeInt",
+            DataType::Int16 => "computeSmallInt",
+            DataType::Int8 => "computeTinyInt",
+            DataType::Float32 => "computeFloat",
+            DataType::Float64 => "computeDouble",
+            DataType::Boolean => "computeBoolean",
+            DataType::Utf8 => "computeVarChar",
+            DataType::LargeUtf8 => "computeLargeVarChar",
+            DataType::Utf8View => "computeViewVarChar",
+            DataType::Binary => "computeVarBinary",
+            DataType::Date32 => "computeDateDay",
+            DataType::Date64 => "computeDateMilli",
+            DataType::Time64(TimeUnit::Microsecond) => "computeTimeMicro",
+            DataType::Time64(TimeUnit::Nanosecond) => "computeTimeNano",
+            DataType::Interval(IntervalUnit::YearMonth) => "computeIntervalYear",
+            DataType::Interval(IntervalUnit::DayTime) => "computeIntervalDay",
+            DataType::Interval(IntervalUnit::MonthDayNano) => "computeIntervalMonthDayNano",
+            // time unit and time zone are taken from exported return type
+            DataType::Timestamp(_, _) => {
+                return Ok(JvmMethod::with_return_type("computeTimeStamp", return_type))
+            }
+            // precision and scale are taken from exported return type
+            DataType::Decimal128(_, _) => {
+                return Ok(JvmMethod::with_return_type("computeDecimal", return_type))
+            }
+            DataType::Decimal256(_, _) => {
+                return Ok(JvmMethod::with_return_type(
+                    "computeDecimal256",
+                    return_type,
+                ))
+            }
+            // nested vectors are created from exported return type
+            DataType::List(_)
+            | DataType::LargeList(_)
+            | DataType::FixedSizeList(_, _)
+            | DataType::Struct(_)
+            | DataType::Map(_, _) => {
+                return Ok(JvmMethod::with_return_type("computeNested", return_type))
+            }
+            _ => exec_err!("type not supported: {return_type}")?,
         };
 
-        Ok(method_name.into())
+        Ok(JvmMethod::new(method_name))
     }
 }
 
 #[derive(Debug)]
 struct JvmFunctionWrapper {
     name: String,
-    argument_types: Vec<DataType>,
     signature: Signature,
-    return_type: DataType,
-    function_definition: FunctionDefinition,
-    inner: JvmFunction,
+    /// java function per argument types
+    overloads: Vec<Arc<JvmOverload>>,
+}
+
+impl JvmFunctionWrapper {
+    /// Creates function with given overloads, and a new one
+    fn new(name: String, mut overloads: Vec<Arc<JvmOverload>>, overload: JvmOverload) -> Self {
+        overloads.push(Arc::new(overload));
+
+        // function is as volatile as its most volatile overload
+        let volatility = overloads
+            .iter()
+            .map(|o| o.volatility)
+            .max()
+            .unwrap_or(Volatility::Volatile);
+
+        let signature = match overloads.as_slice() {
+            [overload] => Signature::new(overload.type_signature.clone(), volatility),
+            overloads => Signature::one_of(
+                overloads.iter().map(|o| o.type_signature.clone()).collect(),
+                volatility,
+            ),
+        };
+
+        Self {
+            name,
+            signature,
+            overloads,
+        }
+    }
+
+    /// Finds overload for given argument types,
+    /// arguments are coerced to one of overloads
+    fn overload(&self, arg_types: &[DataType]) -> Result<&JvmOverload> {
+        match self
+            .overloads
+            .iter()
+            .find(|o| signature_accepts(&o.type_signature, arg_types))
+        {
+            Some(overload) => Ok(overload),
+            None => exec_err!(
+                "function [{}] has no overload for arguments ({})",
+                self.name,
+                arg_types
+                    .iter()
+                    .map(|t| t.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
 }
 
 impl ScalarUDFImpl for JvmFunctionWrapper {
@@ -115,27 +317,541 @@ impl ScalarUDFImpl for JvmFunctionWrapper {
 
     fn return_type(
         &self,
-        _arg_types: &[datafusion::arrow::datatypes::DataType],
+        arg_types: &[datafusion::arrow::datatypes::DataType],
     ) -> Result<datafusion::arrow::datatypes::DataType> {
-        Ok(self.return_type.clone())
+        Ok(self.overload(arg_types)?.return_type.clone())
+    }
+
+    /// java function may compute return type from
+    /// argument types and literal arguments
+    fn return_type_from_args(&self, args: ReturnTypeArgs) -> Result<ReturnInfo> {
+        self.overload(args.arg_types)?.return_info(&args)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let arg_types = args.args.iter().map(|a| a.data_type()).collect::<Vec<_>>();
+
+        self.overload(&arg_types)?.invoke(
+            &self.name,
+            &args.args,
+            args.number_rows,
+            args.return_type,
+        )
+    }
+}
+
+/// Java function invoked for given argument types
+#[derive(Debug)]
+struct JvmOverload {
+    type_signature: TypeSignature,
+    volatility: Volatility,
+    /// declared return type
+    return_type: DataType,
+    function_definition: FunctionDefinition,
+    inner: JvmFunction,
+    /// row by row function invoked with return type other than declared,
+    /// entry point depends on return type (computed by java)
+    methods: Mutex<HashMap<DataType, Arc<JvmFunction>>>,
+    /// return types computed by java, per argument types and literal
+    /// arguments, as return type is requested whenever query is planned
+    return_types: Mutex<HashMap<ReturnTypeKey, (DataType, bool)>>,
+}
+
+/// argument types and literal arguments return type is computed from
+type ReturnTypeKey = (Vec<DataType>, Vec<Option<ScalarValue>>);
+
+impl JvmOverload {
+    /// Function entry point for given return type,
+    /// resolved once per return type
+    fn function_with_method(&self, return_type: &DataType) -> Result<Arc<JvmFunction>> {
+        let mut methods = self.methods.lock().expect("methods lock");
+
+        if let Some(function) = methods.get(return_type) {
+            return Ok(function.clone());
+        }
+
+        let method = JvmFunctionFactory::return_type_to_method(return_type)?;
+        let function = Arc::new(self.inner.with_method(&method)?);
+        methods.insert(return_type.clone(), function.clone());
+
+        Ok(function)
+    }
+
+    /// Return type computed by java, or declared return type
+    fn return_info(&self, args: &ReturnTypeArgs) -> Result<ReturnInfo> {
+        if !self.inner.computes_return_field() {
+            return Ok(ReturnInfo::new_nullable(self.return_type.clone()));
+        }
+
+        let key = (
+            args.arg_types.to_vec(),
+            args.scalar_arguments
+                .iter()
+                .map(|s| s.cloned())
+                .collect::<Vec<_>>(),
+        );
+        let mut return_types = self.return_types.lock().expect("return types lock");
+
+        if let Some((data_type, nullable)) = return_types.get(&key) {
+            return Ok(ReturnInfo::new(data_type.clone(), *nullable));
+        }
+
+        let (data_type, nullable) = self.return_field(args)?;
+        return_types.insert(key, (data_type.clone(), nullable));
+
+        Ok(ReturnInfo::new(data_type, nullable))
+    }
+
+    /// Return type (and nullability) computed by java
+    fn return_field(&self, args: &ReturnTypeArgs) -> Result<(DataType, bool)> {
+        // literal arguments are passed as a single row,
+        // other arguments are null
+        let arrays = args
+            .arg_types
+            .iter()
+            .zip(args.scalar_arguments)
+            .map(|(t, s)| match s {
+                Some(s) => s.cast_to(t)?.to_array_of_size(1),
+                None => Ok(new_null_array(t, 1)),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let array = JvmFunction::create_arrow_data_with_len(args.arg_types, &arrays, 1)?;
+
+        match self.inner.return_field_java(&array)? {
+            Some(field) => Ok((field.data_type().clone(), field.is_nullable())),
+            None => Ok((self.return_type.clone(), true)),
+        }
     }
 
     fn invoke(
         &self,
-        args: &[datafusion::logical_expr::ColumnarValue],
-    ) -> Result<datafusion::logical_expr::ColumnarValue> {
-        let arrays = ColumnarValue::values_to_arrays(args)?;
-        let array = JvmFunction::create_arrow_data(&self.argument_types, &arrays)?;
+        name: &str,
+        args: &[ColumnarValue],
+        number_rows: usize,
+        return_type: &DataType,
+    ) -> Result<ColumnarValue> {
+        let scalars = args.iter().all(|a| matches!(a, ColumnarValue::Scalar(_)));
+
+        // when all arguments are scalars java is invoked once, for a single row,
+        // rather than for a batch of equal rows. Volatile functions may return
+        // different value for each row, so they are invoked for all rows.
+        if scalars && !args.is_empty() && self.volatility != Volatility::Volatile {
+            let arrays = args
+                .iter()
+                .map(|a| a.to_array(1))
+                .collect::<Result<Vec<_>>>()?;
+            let result = self.invoke_arrays(name, &arrays, 1, return_type)?;
+
+            return Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                &result, 0,
+            )?));
+        }
+
+        // constant arguments are passed as they are to functions accepting them,
+        // other functions read arguments row by row, so constants are expanded
+        let arrays = args
+            .iter()
+            .map(|a| match a {
+                ColumnarValue::Scalar(s) if self.inner.accepts_constants() => {
+                    constant_array(s, number_rows)
+                }
+                a => a.to_array(number_rows),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let result = self.invoke_arrays(name, &arrays, number_rows, return_type)?;
+
+        Ok(ColumnarValue::from(result))
+    }
+
+    fn invoke_arrays(
+        &self,
+        name: &str,
+        arrays: &[ArrayRef],
+        number_rows: usize,
+        return_type: &DataType,
+    ) -> Result<ArrayRef> {
+        // row by row function entry point depends on return type,
+        // which may be computed by java when query is planned
+        let function_with_method;
+        let function =
+            if self.inner.kind() == JvmFunctionKind::Scalar && return_type != &self.return_type {
+                function_with_method = self.function_with_method(return_type)?;
+                function_with_method.as_ref()
+            } else {
+                &self.inner
+            };
+
+        let result = if function.is_strict() {
+            Self::invoke_strict(function, arrays, number_rows, return_type)?
+        } else {
+            let array =
+                JvmFunction::create_arrow_data_with_len(&data_types(arrays), arrays, number_rows)?;
+            function.invoke_java(array)?
+        };
+
+        // vectorized functions create result on their own,
+        // so we make sure result matches function definition
+        if function.kind() == JvmFunctionKind::VectorizedScalar {
+            // nested field names may differ (like list item name),
+            // layout compatible result is cast to expected field names
+            if !result.data_type().equals_datatype(return_type) {
+                exec_err!(
+                    "function [{}] returned [{}], expected [{}]",
+                    name,
+                    result.data_type(),
+                    return_type
+                )?
+            }
+            if result.len() != number_rows {
+                exec_err!(
+                    "function [{}] returned {} rows, expected {}",
+                    name,
+                    result.len(),
+                    number_rows
+                )?
+            }
+            if result.data_type() != return_type {
+                return Ok(cast(&result, return_type)?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Invokes java only for rows where all arguments are not null,
+    /// result for other rows is null
+    fn invoke_strict(
+        function: &JvmFunction,
+        arrays: &[ArrayRef],
+        number_rows: usize,
+        return_type: &DataType,
+    ) -> Result<ArrayRef> {
+        let nulls = arrays.iter().fold(None, |nulls, a| {
+            NullBuffer::union(nulls.as_ref(), a.logical_nulls().as_ref())
+        });
+
+        match nulls {
+            Some(nulls) if nulls.null_count() == number_rows => {
+                Ok(new_null_array(return_type, number_rows))
+            }
+            Some(nulls) => {
+                let predicate = BooleanArray::new(nulls.inner().clone(), None);
+                let arrays = arrays
+                    .iter()
+                    .map(|a| filter(a, &predicate))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+
+                let array = JvmFunction::create_arrow_data_with_len(
+                    &data_types(&arrays),
+                    &arrays,
+                    number_rows - nulls.null_count(),
+                )?;
+                let result = function.invoke_java(array)?;
+
+                // put results back to their rows,
+                // rows with null arguments get null index
+                let mut next = 0;
+                let indices = nulls
+                    .iter()
+                    .map(|valid| {
+                        valid.then(|| {
+                            next += 1;
+                            next - 1
+                        })
+                    })
+                    .collect::<UInt32Array>();
+
+                Ok(take(&result, &indices, None)?)
+            }
+            None => {
+                let array = JvmFunction::create_arrow_data_with_len(
+                    &data_types(arrays),
+                    arrays,
+                    number_rows,
+                )?;
+                Ok(function.invoke_java(array)?)
+            }
+        }
+    }
+}
+
+/// constant argument of given number of rows, as run end
+/// encoded array with a single run (single value)
+fn constant_array(value: &ScalarValue, number_rows: usize) -> Result<ArrayRef> {
+    if number_rows == 0 {
+        return value.to_array_of_size(0);
+    }
+
+    let run_ends = Int32Array::from(vec![i32::try_from(number_rows).map_err(|_| {
+        DataFusionError::Execution(format!(
+            "too many rows for constant argument: {number_rows}"
+        ))
+    })?]);
+    let values = value.to_array_of_size(1)?;
+
+    Ok(Arc::new(RunArray::try_new(&run_ends, &values)?))
+}
+
+/// actual argument types, which may differ from declared
+/// ones for variadic and coerced signatures
+fn data_types(arrays: &[ArrayRef]) -> Vec<DataType> {
+    arrays.iter().map(|a| a.data_type().clone()).collect()
+}
+
+#[derive(Debug)]
+struct JvmAggregateWrapper {
+    name: String,
+    signature: Signature,
+    return_type: DataType,
+    function_definition: FunctionDefinition,
+    class: JvmClass,
+}
+
+impl AggregateUDFImpl for JvmAggregateWrapper {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    fn accumulator(&self, _acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(JvmAccumulatorWrapper {
+            inner: JvmAccumulator::try_new(&self.class, &self.return_type)?,
+        }))
+    }
+
+    /// java accumulator state is opaque to datafusion,
+    /// it is kept as a single binary value
+    fn state_fields(&self, args: StateFieldsArgs) -> Result<Vec<Field>> {
+        Ok(vec![Field::new(
+            format_state_name(args.name, "state"),
+            DataType::Binary,
+            true,
+        )])
+    }
+}
+
+#[derive(Debug)]
+struct JvmAccumulatorWrapper {
+    inner: JvmAccumulator,
+}
+
+impl Accumulator for JvmAccumulatorWrapper {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let array = JvmFunction::create_arrow_data(&data_types(values), values)?;
+        Ok(self.inner.update_java(array)?)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let array = JvmFunction::create_arrow_data(&[DataType::Binary], states)?;
+        Ok(self.inner.merge_java(array)?)
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let state = self.inner.state_java()?;
+        Ok(vec![ScalarValue::try_from_array(&state, 0)?])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let result = self.inner.evaluate_java()?;
+        ScalarValue::try_from_array(&result, 0)
+    }
+
+    /// size of java accumulator is not known
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+#[derive(Debug)]
+struct JvmWindowWrapper {
+    name: String,
+    signature: Signature,
+    return_type: DataType,
+    function_definition: FunctionDefinition,
+    class: JvmClass,
+}
+
+impl WindowUDFImpl for JvmWindowWrapper {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn partition_evaluator(
+        &self,
+        _partition_evaluator_args: PartitionEvaluatorArgs,
+    ) -> Result<Box<dyn PartitionEvaluator>> {
+        Ok(Box::new(JvmPartitionEvaluatorWrapper {
+            inner: JvmPartitionEvaluator::try_new(&self.class, &self.return_type)?,
+            partition: None,
+        }))
+    }
+
+    fn field(&self, field_args: WindowUDFFieldArgs) -> Result<Field> {
+        Ok(Field::new(
+            field_args.name(),
+            self.return_type.clone(),
+            true,
+        ))
+    }
+}
+
+#[derive(Debug)]
+struct JvmPartitionEvaluatorWrapper {
+    inner: JvmPartitionEvaluator,
+    /// partition exported to java, for window frame evaluation
+    partition: Option<Vec<ArrayRef>>,
+}
+
+impl PartitionEvaluator for JvmPartitionEvaluatorWrapper {
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<ArrayRef> {
+        let array = JvmFunction::create_arrow_data(&data_types(values), values)?;
+        let result = self.inner.evaluate_all_java(array)?;
+
+        if result.len() != num_rows {
+            exec_err!(
+                "window function returned {} rows, expected {}",
+                result.len(),
+                num_rows
+            )?
+        }
+
+        Ok(result)
+    }
+
+    fn evaluate(&mut self, values: &[ArrayRef], range: &Range<usize>) -> Result<ScalarValue> {
+        // the same partition is evaluated for each row window frame,
+        // so it is exported to java only when it changes
+        let exported = self.partition.as_ref().is_some_and(|partition| {
+            partition.len() == values.len()
+                && partition.iter().zip(values).all(|(p, v)| Arc::ptr_eq(p, v))
+        });
+
+        if !exported {
+            let array = JvmFunction::create_arrow_data(&data_types(values), values)?;
+            self.inner.set_partition_java(array)?;
+            self.partition = Some(values.to_vec());
+        }
+
+        let result = self.inner.evaluate_java(range.start, range.end)?;
+
+        ScalarValue::try_from_array(&result, 0)
+    }
+
+    fn uses_window_frame(&self) -> bool {
+        self.inner.uses_window_frame()
+    }
+}
+
+#[derive(Debug)]
+struct JvmTableFunction {
+    class: JvmClass,
+}
+
+impl TableFunctionImpl for JvmTableFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let arguments = args
+            .iter()
+            .map(|a| match a {
+                Expr::Literal(value) => value.to_array(),
+                _ => plan_err!("table function arguments should be literals, got: {a}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let argument_types = arguments
+            .iter()
+            .map(|a| a.data_type().clone())
+            .collect::<Vec<_>>();
+
+        let arguments = JvmFunction::create_arrow_data(&argument_types, &arguments)?;
+        let source = JvmTableSource::try_new(&self.class)?;
+        let schema = source.schema_java(&arguments)?;
+
+        let partition = JvmPartitionStream {
+            schema: schema.clone(),
+            arguments,
+            source: Arc::new(source),
+        };
+
+        Ok(Arc::new(StreamingTable::try_new(
+            schema,
+            vec![Arc::new(partition)],
+        )?))
+    }
+}
+
+#[derive(Debug)]
+struct JvmPartitionStream {
+    schema: SchemaRef,
+    arguments: ArrayData,
+    source: Arc<JvmTableSource>,
+}
+
+impl PartitionStream for JvmPartitionStream {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let mut builder = RecordBatchReceiverStream::builder(self.schema.clone(), 2);
+        let tx = builder.tx();
+        let schema = self.schema.clone();
+        let arguments = self.arguments.clone();
+        let source = self.source.clone();
+
+        // reading from java stream blocks
+        builder.spawn_blocking(move || {
+            let reader = source.stream_java(&arguments)?;
+            if reader.schema().fields() != schema.fields() {
+                exec_err!(
+                    "table function returned schema [{}], expected [{}]",
+                    reader.schema(),
+                    schema
+                )?
+            }
+
+            for batch in reader {
+                // receiver is gone, no need to read further
+                if tx.blocking_send(batch.map_err(Into::into)).is_err() {
+                    break;
+                }
+            }
 
-        let result = self.inner.invoke_java(array)?;
+            Ok(())
+        });
 
-        Ok(ColumnarValue::from(result as ArrayRef))
+        builder.build()
     }
 }
 
 impl From<JvmFunctionError> for DataFusionError {
     fn from(error: JvmFunctionError) -> Self {
-        DataFusionError::Execution(error.to_string())
+        match error {
+            // kept as is, so java exception details and compiler
+            // diagnostics can be retrieved by downcasting
+            JvmFunctionError::JvmException { .. } | JvmFunctionError::JavaCompilationError(_) => {
+                DataFusionError::External(Box::new(error))
+            }
+            error => DataFusionError::Execution(error.to_string()),
+        }
     }
 }
 
@@ -1,257 +1,1733 @@
-use crate::JvmFunctionError;
+use crate::{
+    locator::locate_libjvm, ErrorPolicy, JavaDiagnostic, JavaThrowable, JvmConfig, JvmFunctionError,
+};
 use datafusion::arrow::{
     array::{make_array, Array, ArrayData, ArrayDataBuilder, ArrayRef},
-    datatypes::{DataType, Field, Fields},
+    datatypes::{DataType, Field, Fields, Schema, SchemaRef},
+    error::ArrowError,
     ffi::{from_ffi, to_ffi, FFI_ArrowArray, FFI_ArrowSchema},
+    ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream},
 };
+use datafusion::logical_expr::TypeSignature;
 use jni::{
-    objects::{GlobalRef, JMethodID, JValue},
+    objects::{GlobalRef, JClass, JMethodID, JObject, JObjectArray, JString, JValue},
     signature::ReturnType,
-    InitArgs, InitArgsBuilder, JNIEnv, JNIVersion, JavaVM,
+    sys::jvalue,
+    InitArgs, JNIEnv, JNIVersion, JavaVM,
 };
 use std::{
+    collections::HashMap,
     fmt::Debug,
     ptr::{addr_of, addr_of_mut},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
 };
 
 pub(crate) type Result<T> = std::result::Result<T, JvmFunctionError>;
 
 use once_cell::sync::OnceCell;
 
-// in practice we don't need once cell
-// but test complains as they start multiple JvmFunctionFactory
-// instances which start multiple JVMs.
-// At the moment it looks like there is a
-// one JVM per process limitation
-static JVM: OnceCell<Arc<JavaVM>> = OnceCell::new();
+// in practice we don't need once cell
+// but test complains as they start multiple JvmFunctionFactory
+// instances which start multiple JVMs.
+// At the moment it looks like there is a
+// one JVM per process limitation
+static JVM: OnceCell<StartedJvm> = OnceCell::new();
+
+/// process wide JVM, with options it has been started with
+#[derive(Debug)]
+struct StartedJvm {
+    jvm: Arc<JavaVM>,
+    /// options are not known if JVM has been
+    /// started from [InitArgs]
+    options: Option<Vec<String>>,
+}
+
+impl StartedJvm {
+    fn start(jvm_args: InitArgs, options: Option<Vec<String>>) -> Result<Self> {
+        let libjvm = locate_libjvm()?;
+        log::info!("loading JVM from: {}", libjvm.display());
+
+        match JavaVM::with_libjvm(jvm_args, || Ok(libjvm)) {
+            Ok(jvm) => Ok(Self {
+                jvm: Arc::new(jvm),
+                options,
+            }),
+            Err(e) => {
+                log::error!("JVM failed to start: {}", e);
+                Err(JvmFunctionError::from(e))
+            }
+        }
+    }
+}
+
+/// type of base class all function must extend
+static BASE_CLASS_TYPE: &str = "com/github/milenkovicm/adhesive/Adhesive";
+/// interface of all function base classes
+static FUNCTION_INTERFACE_TYPE: &str = "com/github/milenkovicm/adhesive/AdhesiveFunction";
+/// type of base class vectorized functions extend
+static VECTORIZED_BASE_CLASS_TYPE: &str = "com/github/milenkovicm/adhesive/VectorizedAdhesive";
+/// type of base class aggregate functions extend
+static AGGREGATE_BASE_CLASS_TYPE: &str = "com/github/milenkovicm/adhesive/AdhesiveAccumulator";
+/// type of base class window functions extend
+static WINDOW_BASE_CLASS_TYPE: &str = "com/github/milenkovicm/adhesive/AdhesiveWindow";
+/// type of base class table functions extend
+static TABLE_BASE_CLASS_TYPE: &str = "com/github/milenkovicm/adhesive/AdhesiveTable";
+/// annotation declaring function signature
+static SIGNATURE_ANNOTATION_CLASS_NAME: &str = "com/github/milenkovicm/adhesive/AdhesiveSignature";
+/// kind of declared function signature
+static SIGNATURE_KIND_CLASS_NAME: &str = "com/github/milenkovicm/adhesive/AdhesiveSignature$Kind";
+/// error policy declared by function
+static ERROR_POLICY_CLASS_NAME: &str = "com/github/milenkovicm/adhesive/ErrorPolicy";
+/// java compiler class implementation
+static COMPILER_CLASS_NAME: &str = "com/github/milenkovicm/adhesive/Compiler";
+/// compiler method
+static COMPILER_METHOD_NAME: &str = "compile";
+/// exception thrown by compiler when code can't be compiled
+static COMPILATION_EXCEPTION_CLASS_NAME: &str =
+    "com/github/milenkovicm/adhesive/CompilationException";
+
+/// Java method (entry point) computing function result.
+///
+/// Entry point is selected based on function return type.
+/// Most of entry points know which result vector to create
+/// from their name, for the rest (like timestamps with time zone)
+/// return type is exported to java as an additional argument.
+#[derive(Debug, Clone)]
+pub(crate) struct JvmMethod {
+    name: String,
+    return_type: Option<DataType>,
+}
+
+impl JvmMethod {
+    /// entry point with `(JJJJ)J` signature, returning number of failed rows
+    pub(crate) fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            return_type: None,
+        }
+    }
+
+    /// entry point with `(JJJJJ)J` signature, last argument
+    /// is address of exported return type schema
+    pub(crate) fn with_return_type(name: &str, return_type: &DataType) -> Self {
+        Self {
+            name: name.to_string(),
+            return_type: Some(return_type.clone()),
+        }
+    }
+
+    fn signature(&self) -> &'static str {
+        match self.return_type {
+            Some(_) => "(JJJJJ)J",
+            None => "(JJJJ)J",
+        }
+    }
+}
+
+/// failed rows of function overload, accepting given signature
+type OverloadFailures = (TypeSignature, Arc<AtomicU64>);
+
+#[derive(Debug)]
+pub struct JvmFunctionFactory {
+    /// JNI version used to start JVM, if not already started
+    jni_version: JNIVersion,
+    /// options used to start JVM, if not already started
+    jvm_options: Vec<String>,
+    /// JVM is started lazily, when first function is created
+    jvm: OnceCell<Arc<JavaVM>>,
+    /// number of failed rows, per function overload
+    /// (functions created with [ErrorPolicy::Skip])
+    failures: Mutex<HashMap<String, Vec<OverloadFailures>>>,
+}
+
+impl JvmFunctionFactory {
+    /// Creates factory with given jar on the class path.
+    ///
+    /// JVM is not started until first function is created,
+    /// use [JvmFunctionFactory::warm_up] to start it eagerly.
+    pub fn new_with_jar(jar_path: &str) -> Result<Self> {
+        Self::new_with_config(JvmConfig::new().with_class_path(jar_path))
+    }
+
+    /// Creates factory with given JVM configuration.
+    ///
+    /// Configuration is validated immediately, JVM is
+    /// not started until first function is created.
+    pub fn new_with_config(config: JvmConfig) -> Result<Self> {
+        // this guy complains a lot
+        // we shut him down for now
+        //"-Xcheck:jni".to_string(),
+        let jvm_options = config.jvm_options()?;
+
+        // options are validated now, rather than on first use
+        JvmConfig::init_args(config.jni_version(), &jvm_options)?;
+
+        Ok(Self {
+            jni_version: config.jni_version(),
+            jvm_options,
+            jvm: OnceCell::new(),
+            failures: Mutex::default(),
+        })
+    }
+
+    /// Creates factory from already built JVM arguments,
+    /// JVM is started immediately.
+    ///
+    /// There is one JVM per process, if it has already been started
+    /// given arguments are ignored.
+    pub fn new_from_args(jvm_args: InitArgs) -> Result<Self> {
+        let mut started = false;
+        let jvm = JVM.get_or_try_init(|| {
+            started = true;
+            StartedJvm::start(jvm_args, None)
+        })?;
+
+        if !started {
+            log::warn!(
+                "JVM has already been started, arguments of new factory are ignored (JVM started with options: {:?})",
+                jvm.options
+            );
+        }
+
+        Ok(Self {
+            jni_version: JNIVersion::V8,
+            jvm_options: vec![],
+            jvm: OnceCell::with_value(jvm.jvm.clone()),
+            failures: Mutex::default(),
+        })
+    }
+
+    /// Starts JVM (if not already started), for services which
+    /// prefer to fail fast rather than on first function creation
+    pub fn warm_up(&self) -> Result<()> {
+        self.jvm().map(|_| ())
+    }
+
+    /// Returns JVM, starting it on first use.
+    ///
+    /// There is one JVM per process, if it has already been started
+    /// (by another factory) it is reused, as long as it has been started
+    /// with the same options.
+    fn jvm(&self) -> Result<&Arc<JavaVM>> {
+        self.jvm.get_or_try_init(|| {
+            let jvm = JVM.get_or_try_init(|| {
+                log::info!("starting JVM with options: {:?}", self.jvm_options);
+                let jvm_args = JvmConfig::init_args(self.jni_version, &self.jvm_options)?;
+
+                StartedJvm::start(jvm_args, Some(self.jvm_options.clone()))
+            })?;
+
+            match &jvm.options {
+                Some(options) if *options != self.jvm_options => {
+                    Err(JvmFunctionError::IncompatibleJvmError {
+                        started: options.clone(),
+                        requested: self.jvm_options.clone(),
+                    })
+                }
+                Some(_) => Ok(jvm.jvm.clone()),
+                None => {
+                    log::warn!(
+                        "JVM has been started from arguments, can't check if options {:?} are compatible",
+                        self.jvm_options
+                    );
+                    Ok(jvm.jvm.clone())
+                }
+            }
+        })
+    }
+
+    /// Number of rows function failed to compute, for functions
+    /// created with [ErrorPolicy::Skip]. Failed rows have null result.
+    ///
+    /// Failed rows of all function overloads are summed up.
+    pub fn failed_rows(&self, function_name: &str) -> u64 {
+        self.failures
+            .lock()
+            .expect("failures lock")
+            .get(function_name)
+            .map(|overloads| {
+                overloads
+                    .iter()
+                    .map(|(_, f)| f.load(Ordering::Relaxed))
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Number of rows function overload invoked
+    /// with given argument types failed to compute
+    pub fn overload_failed_rows(&self, function_name: &str, argument_types: &[DataType]) -> u64 {
+        self.failures
+            .lock()
+            .expect("failures lock")
+            .get(function_name)
+            .and_then(|overloads| {
+                overloads
+                    .iter()
+                    .find(|(signature, _)| signature_accepts(signature, argument_types))
+            })
+            .map(|(_, f)| f.load(Ordering::Relaxed))
+            .unwrap_or_default()
+    }
+
+    /// Tracks failed rows of given function overload,
+    /// replaced function starts from zero
+    pub(crate) fn track_failures(
+        &self,
+        function_name: &str,
+        type_signature: &TypeSignature,
+        function: &JvmFunction,
+        overload: bool,
+    ) {
+        let mut failures = self.failures.lock().expect("failures lock");
+        let tracked = (type_signature.clone(), function.failures.clone());
+
+        match failures.get_mut(function_name) {
+            Some(overloads) if overload => overloads.push(tracked),
+            _ => {
+                failures.insert(function_name.to_string(), vec![tracked]);
+            }
+        }
+    }
+
+    /// Loads (compiled) function class
+    pub(crate) fn load_class(&self, class_name: &str) -> Result<JvmClass> {
+        log::info!("load function class: [{}]", class_name);
+        let jvm = self.jvm()?;
+        let mut env = _attach_tread(jvm)?;
+
+        let class_name = class_name.replace('.', "/");
+        let clazz = env
+            .find_class(&class_name)
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        JvmClass::new(&mut env, &clazz, &class_name, jvm)
+    }
+
+    /// Compiles java code to function class
+    pub(crate) fn compile_class(&self, java_code: &str) -> Result<JvmClass> {
+        let jvm = self.jvm()?;
+        let mut env = _attach_tread(jvm)?;
+
+        let compiler_clazz = env
+            .find_class(COMPILER_CLASS_NAME)
+            .map_err(|e| jni_error(&mut env, e))?;
+        let compiler_signature = "(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/Class;";
+
+        let compile_method_id = env
+            .get_method_id(&compiler_clazz, COMPILER_METHOD_NAME, compiler_signature)
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        // compiler is a singleton
+        // look for static filed called `INSTANCE`
+        let compiler_instance = env
+            .get_static_field(
+                COMPILER_CLASS_NAME,
+                "INSTANCE",
+                format!("L{COMPILER_CLASS_NAME};"),
+            )
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        let (java_code, fqn) = crate::util::update_java_code(java_code)?;
+
+        let new_class_name = env.new_string(&fqn)?;
+        let new_class_definition = env.new_string(java_code)?;
+        let new_class_name = JValue::Object(&new_class_name).as_jni();
+        let new_class_definition = JValue::Object(&new_class_definition).as_jni();
+
+        let result = unsafe {
+            env.call_method_unchecked(
+                &compiler_instance.l()?,
+                compile_method_id,
+                ReturnType::Object,
+                &[new_class_name, new_class_definition],
+            )
+        };
+
+        let result = match result {
+            Err(jni::errors::Error::JavaException) => {
+                let throwable = env.exception_occurred()?;
+                env.exception_clear()?;
+
+                // compilation errors are reported with compiler diagnostics
+                let error = if env.is_instance_of(&throwable, COMPILATION_EXCEPTION_CLASS_NAME)? {
+                    JvmFunctionError::JavaCompilationError(compilation_diagnostics(
+                        &mut env, &throwable,
+                    )?)
+                } else {
+                    java_exception(&mut env, &throwable)
+                };
+
+                return Err(error);
+            }
+            result => result?.l()?,
+        };
+
+        let clazz = JClass::from(result);
+
+        JvmClass::new(&mut env, &clazz, &fqn, jvm)
+    }
+
+    /// Resolves function argument and return types, from types function
+    /// is created with and signature declared by function class (`@AdhesiveSignature`).
+    ///
+    /// Types function is created with take precedence,
+    /// if both are provided they should match.
+    pub(crate) fn resolve_signature(
+        &self,
+        class: &JvmClass,
+        argument_types: Option<Vec<DataType>>,
+        return_type: Option<DataType>,
+    ) -> Result<(TypeSignature, DataType)> {
+        let declared = class.declared_signature()?;
+
+        let type_signature = match (argument_types, &declared) {
+            (Some(argument_types), Some(declared))
+                if !signature_accepts(&declared.type_signature, &argument_types) =>
+            {
+                return Err(JvmFunctionError::SignatureMismatch {
+                    class: class.name.clone(),
+                    signature: declared.type_signature.to_string_repr().join(", "),
+                    actual: argument_types,
+                })
+            }
+            (Some(argument_types), _) => TypeSignature::Exact(argument_types),
+            (None, Some(declared)) => declared.type_signature.clone(),
+            (None, None) => TypeSignature::Exact(vec![]),
+        };
+
+        let return_type = match (return_type, &declared) {
+            (Some(return_type), Some(declared)) if return_type != declared.return_type => {
+                return Err(JvmFunctionError::ReturnTypeMismatch {
+                    class: class.name.clone(),
+                    expected: declared.return_type.clone(),
+                    actual: return_type,
+                })
+            }
+            (Some(return_type), _) => return_type,
+            (None, Some(declared)) => declared.return_type.clone(),
+            (None, None) => return Err(invalid_class(
+                &class.name,
+                "return type should be provided with RETURNS, or declared with @AdhesiveSignature",
+            )),
+        };
+
+        // input schema describes exact arguments
+        if let TypeSignature::Exact(argument_types) = &type_signature {
+            self.verify_argument_types(class, argument_types)?;
+        }
+
+        Ok((type_signature, return_type))
+    }
+
+    /// Checks function argument types against input schema declared by
+    /// function class, if any (static `inputSchema` method)
+    fn verify_argument_types(&self, class: &JvmClass, argument_types: &[DataType]) -> Result<()> {
+        let expected = match class.input_schema()? {
+            Some(schema) => schema
+                .fields()
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect::<Vec<_>>(),
+            None => return Ok(()),
+        };
+
+        if expected != argument_types {
+            return Err(JvmFunctionError::ArgumentTypeMismatch {
+                class: class.name.clone(),
+                expected,
+                actual: argument_types.to_vec(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Creates function instance, which will be invoked using given method.
+    ///
+    /// Given (session) error policy is used, unless
+    /// function class declares its own.
+    pub(crate) fn create_function(
+        &self,
+        class: &JvmClass,
+        method: &JvmMethod,
+        error_policy: ErrorPolicy,
+    ) -> Result<JvmFunction> {
+        log::info!(
+            "create function for class: [{}] and method: [{}]",
+            class.name,
+            method.name
+        );
+        let mut env = _attach_tread(&class.jvm)?;
+        let clazz = <&JClass>::from(class.class_global_ref.as_obj());
+
+        let method_id = env
+            .get_method_id(clazz, &method.name, method.signature())
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        // we use default constructor to create this class
+        //
+        // an alternative was to let user define singleton `INSTANCE`.
+        // for now we will allocate new object and cache global reference.
+        //
+        // alternative was to allocate instance without calling constructor,
+        // which we avoided as user might want to use constructor to init class
+        // let new_object_instance = env.alloc_object(&clazz)?;
+
+        let new_object_instance = env
+            .new_object(clazz, "()V", &[])
+            .map_err(|e| jni_error(&mut env, e))?;
+        let strict = is_strict(&mut env, &new_object_instance)?;
+        // row by row functions read arguments row by row, so
+        // only vectorized functions can accept constant arguments
+        let constants = class.kind == JvmFunctionKind::VectorizedScalar
+            && accepts_constant_arguments(&mut env, &new_object_instance)?;
+        let return_field = computes_return_field(&mut env, &new_object_instance)?;
+
+        // only row by row functions handle row failures
+        let error_policy = if class.kind == JvmFunctionKind::Scalar {
+            let error_policy =
+                declared_error_policy(&mut env, &new_object_instance)?.unwrap_or(error_policy);
+            set_error_policy(&mut env, &new_object_instance, error_policy)?;
+            error_policy
+        } else {
+            error_policy
+        };
+
+        // The JNI divides object references used by the native code into two
+        // categories: local and global references. Local references are valid
+        // for the duration of a native method call, and are automatically freed after
+        // the native method returns. Global references remain valid until they are explicitly freed.
+        //
+        // https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/design.html
+
+        let object_global_ref = env.new_global_ref(new_object_instance)?;
+
+        Ok(JvmFunction {
+            target_method_id: method_id,
+            target_object_global_ref: object_global_ref,
+            return_type: method.return_type.clone(),
+            kind: class.kind,
+            strict,
+            constants,
+            return_field,
+            error_policy,
+            failures: Arc::default(),
+            jvm: class.jvm.clone(),
+        })
+    }
+}
+
+/// Kind of java function, defined by base class function class extends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JvmFunctionKind {
+    /// extends [BASE_CLASS_TYPE], invoked row by row
+    Scalar,
+    /// extends [VECTORIZED_BASE_CLASS_TYPE], invoked with all arguments at once
+    VectorizedScalar,
+    /// extends [AGGREGATE_BASE_CLASS_TYPE], instance created per accumulator
+    Aggregate,
+    /// extends [WINDOW_BASE_CLASS_TYPE], instance created per partition
+    Window,
+    /// extends [TABLE_BASE_CLASS_TYPE], instance created per function call
+    Table,
+}
+
+/// Function class (either loaded or compiled)
+#[derive(Debug, Clone)]
+pub(crate) struct JvmClass {
+    name: String,
+    kind: JvmFunctionKind,
+    class_global_ref: GlobalRef,
+    jvm: Arc<JavaVM>,
+}
+
+impl JvmClass {
+    fn new(env: &mut JNIEnv, clazz: &JClass, name: &str, jvm: &Arc<JavaVM>) -> Result<Self> {
+        let kind = if env.is_assignable_from(clazz, VECTORIZED_BASE_CLASS_TYPE)? {
+            JvmFunctionKind::VectorizedScalar
+        } else if env.is_assignable_from(clazz, AGGREGATE_BASE_CLASS_TYPE)? {
+            JvmFunctionKind::Aggregate
+        } else if env.is_assignable_from(clazz, WINDOW_BASE_CLASS_TYPE)? {
+            JvmFunctionKind::Window
+        } else if env.is_assignable_from(clazz, TABLE_BASE_CLASS_TYPE)? {
+            JvmFunctionKind::Table
+        } else if env.is_assignable_from(clazz, BASE_CLASS_TYPE)? {
+            JvmFunctionKind::Scalar
+        } else {
+            return Err(invalid_class(
+                name,
+                "class does not extend any of adhesive base classes",
+            ));
+        };
+
+        verify_instantiable(env, clazz, name)?;
+
+        Ok(Self {
+            name: name.replace('/', "."),
+            kind,
+            class_global_ref: env.new_global_ref(clazz)?,
+            jvm: jvm.clone(),
+        })
+    }
+
+    pub(crate) fn kind(&self) -> JvmFunctionKind {
+        self.kind
+    }
+
+    /// Signature declared by function class, if any (`@AdhesiveSignature`)
+    fn declared_signature(&self) -> Result<Option<DeclaredSignature>> {
+        let mut env = _attach_tread(&self.jvm)?;
+        let clazz = <&JClass>::from(self.class_global_ref.as_obj());
+
+        let annotation_clazz = env
+            .find_class(SIGNATURE_ANNOTATION_CLASS_NAME)
+            .map_err(|e| match jni_error(&mut env, e) {
+                // NoClassDefFoundError
+                JvmFunctionError::JvmException { .. } => JvmFunctionError::AdhesiveClassNotFound(
+                    SIGNATURE_ANNOTATION_CLASS_NAME.replace('/', "."),
+                ),
+                e => e,
+            })?;
+        let annotation = env
+            .call_method(
+                clazz,
+                "getAnnotation",
+                "(Ljava/lang/Class;)Ljava/lang/annotation/Annotation;",
+                &[JValue::Object(&annotation_clazz)],
+            )
+            .map_err(|e| jni_error(&mut env, e))?
+            .l()?;
+
+        if annotation.is_null() {
+            return Ok(None);
+        }
+
+        let kind = env
+            .call_method(
+                &annotation,
+                "kind",
+                format!("()L{SIGNATURE_KIND_CLASS_NAME};"),
+                &[],
+            )
+            .map_err(|e| jni_error(&mut env, e))?
+            .l()?;
+        let kind = env
+            .call_method(&kind, "name", "()Ljava/lang/String;", &[])
+            .map_err(|e| jni_error(&mut env, e))?
+            .l()?;
+        let kind = java_string(&mut env, kind)?;
+
+        let count = env
+            .call_method(&annotation, "count", "()I", &[])
+            .map_err(|e| jni_error(&mut env, e))?
+            .i()?;
+
+        let arguments = env
+            .call_method(&annotation, "arguments", "()[Ljava/lang/String;", &[])
+            .map_err(|e| jni_error(&mut env, e))?
+            .l()?;
+        let arguments = JObjectArray::from(arguments);
+        let len = env.get_array_length(&arguments)?;
+
+        let mut argument_types = Vec::with_capacity(len as usize);
+        for i in 0..len {
+            let argument = env.get_object_array_element(&arguments, i)?;
+            argument_types.push(self.parse_data_type(&java_string(&mut env, argument)?)?);
+        }
+
+        let return_type = env
+            .call_method(&annotation, "returns", "()Ljava/lang/String;", &[])
+            .map_err(|e| jni_error(&mut env, e))?
+            .l()?;
+        let return_type = self.parse_data_type(&java_string(&mut env, return_type)?)?;
+
+        let type_signature = match kind.as_str() {
+            "EXACT" => TypeSignature::Exact(argument_types),
+            "VARIADIC" if !argument_types.is_empty() => TypeSignature::Variadic(argument_types),
+            "VARIADIC_ANY" => TypeSignature::VariadicAny,
+            "UNIFORM" if count > 0 && !argument_types.is_empty() => {
+                TypeSignature::Uniform(count as usize, argument_types)
+            }
+            "NUMERIC" if count > 0 => TypeSignature::Numeric(count as usize),
+            _ => {
+                return Err(invalid_class(
+                    &self.name,
+                    &format!(
+                        "invalid @AdhesiveSignature of kind {kind}, with {} argument types and count {count}",
+                        argument_types.len()
+                    ),
+                ))
+            }
+        };
+
+        Ok(Some(DeclaredSignature {
+            type_signature,
+            return_type,
+        }))
+    }
+
+    fn parse_data_type(&self, data_type: &str) -> Result<DataType> {
+        parse_declared_type(data_type).map_err(|e| {
+            invalid_class(
+                &self.name,
+                &format!("invalid type [{data_type}] in @AdhesiveSignature: {e}"),
+            )
+        })
+    }
+
+    /// Input schema declared by function class, if any.
+    ///
+    /// Schema is declared by static method, so it is read
+    /// without creating function instance.
+    fn input_schema(&self) -> Result<Option<Schema>> {
+        // table functions do not declare input schema
+        if self.kind == JvmFunctionKind::Table {
+            return Ok(None);
+        }
+
+        let mut env = _attach_tread(&self.jvm)?;
+        let clazz = <&JClass>::from(self.class_global_ref.as_obj());
+
+        let mut result_schema = FFI_ArrowSchema::empty();
+        let ptr_result_schema = JValue::from(addr_of_mut!(result_schema) as i64);
+
+        let declared = env
+            .call_static_method(
+                FUNCTION_INTERFACE_TYPE,
+                "exportInputSchema",
+                "(Ljava/lang/Class;J)Z",
+                &[JValue::Object(clazz), ptr_result_schema],
+            )
+            .map_err(|e| jni_error(&mut env, e))?
+            .z()?;
+
+        if declared {
+            Ok(Some(Schema::try_from(&result_schema)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Function signature declared by function class
+#[derive(Debug, Clone, PartialEq)]
+struct DeclaredSignature {
+    type_signature: TypeSignature,
+    return_type: DataType,
+}
+
+/// Parses type declared by function class, arrow type names are
+/// used, with addition of `Map(key, value)` which arrow can't parse
+/// (and SQL can't declare)
+fn parse_declared_type(data_type: &str) -> std::result::Result<DataType, ArrowError> {
+    let map_types = data_type
+        .trim()
+        .strip_prefix("Map(")
+        .and_then(|t| t.strip_suffix(')'));
+
+    let Some(map_types) = map_types else {
+        return data_type.parse();
+    };
+
+    // key and value are separated by top level comma,
+    // as they may contain commas themselves (like decimals)
+    let mut depth = 0;
+    let separator = map_types.find(|c| {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        c == ',' && depth == 0
+    });
+
+    match separator {
+        Some(separator) => {
+            let entries = Field::new_struct(
+                "entries",
+                vec![
+                    Field::new("keys", parse_declared_type(&map_types[..separator])?, false),
+                    Field::new(
+                        "values",
+                        parse_declared_type(&map_types[separator + 1..])?,
+                        true,
+                    ),
+                ],
+                false,
+            );
+            Ok(DataType::Map(Arc::new(entries), false))
+        }
+        None => Err(ArrowError::ParseError(format!(
+            "map should declare key and value types: [{data_type}]"
+        ))),
+    }
+}
+
+/// Checks if (coerced) argument types match signature
+pub(crate) fn signature_accepts(signature: &TypeSignature, arg_types: &[DataType]) -> bool {
+    let uniform = arg_types.windows(2).all(|w| w[0] == w[1]);
+
+    match signature {
+        TypeSignature::Exact(types) => types == arg_types,
+        TypeSignature::Variadic(types) => {
+            !arg_types.is_empty() && uniform && types.contains(&arg_types[0])
+        }
+        TypeSignature::VariadicAny => !arg_types.is_empty(),
+        TypeSignature::Uniform(count, types) => {
+            arg_types.len() == *count && uniform && types.contains(&arg_types[0])
+        }
+        TypeSignature::Numeric(count) => {
+            arg_types.len() == *count && uniform && arg_types.iter().all(|t| t.is_numeric())
+        }
+        TypeSignature::OneOf(signatures) => {
+            signatures.iter().any(|s| signature_accepts(s, arg_types))
+        }
+        _ => false,
+    }
+}
+
+/// `java.lang.reflect.Modifier.INTERFACE`
+const MODIFIER_INTERFACE: i32 = 0x0200;
+/// `java.lang.reflect.Modifier.ABSTRACT`
+const MODIFIER_ABSTRACT: i32 = 0x0400;
+
+fn invalid_class(name: &str, reason: &str) -> JvmFunctionError {
+    JvmFunctionError::InvalidFunctionClass {
+        class: name.replace('/', "."),
+        reason: reason.to_string(),
+    }
+}
+
+/// Checks that function instance can be created,
+/// class is not abstract and it has public default constructor
+fn verify_instantiable(env: &mut JNIEnv, clazz: &JClass, name: &str) -> Result<()> {
+    let modifiers = env
+        .call_method(clazz, "getModifiers", "()I", &[])
+        .map_err(|e| jni_error(env, e))?
+        .i()?;
+
+    if modifiers & MODIFIER_INTERFACE != 0 {
+        return Err(invalid_class(name, "class is an interface"));
+    }
+    if modifiers & MODIFIER_ABSTRACT != 0 {
+        return Err(invalid_class(name, "class is abstract"));
+    }
+
+    let no_parameters = env.new_object_array(0, "java/lang/Class", JObject::null())?;
+    let constructor = env.call_method(
+        clazz,
+        "getConstructor",
+        "([Ljava/lang/Class;)Ljava/lang/reflect/Constructor;",
+        &[JValue::Object(&no_parameters)],
+    );
+
+    match constructor {
+        Ok(_) => Ok(()),
+        // NoSuchMethodException
+        Err(jni::errors::Error::JavaException) => {
+            env.exception_clear()?;
+            Err(invalid_class(
+                name,
+                "class does not have public constructor without parameters",
+            ))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct JvmFunction {
+    target_method_id: JMethodID,
+
+    // TODO: WARN  jni::wrapper::objects::global_ref] Dropping a GlobalRef in a detached thread.
+    // Fix your code if this message appears frequently (see the GlobalRef docs).
+    //
+    // checking current Drop impl for GlobalRef it would do same thing I'll do
+    target_object_global_ref: GlobalRef,
+    /// return type to be exported to java,
+    /// if target method expects it
+    return_type: Option<DataType>,
+    kind: JvmFunctionKind,
+    /// should java be skipped for rows with null arguments
+    strict: bool,
+    /// are constant (scalar) arguments passed as run end encoded
+    /// arrays, rather than expanded to all rows
+    constants: bool,
+    /// does java compute return field from arguments
+    /// (`AdhesiveFunction.returnField` is overridden)
+    return_field: bool,
+    error_policy: ErrorPolicy,
+    /// number of failed rows, reported by java
+    failures: Arc<AtomicU64>,
+    jvm: Arc<JavaVM>,
+}
+
+impl JvmFunction {
+    pub(crate) fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub(crate) fn kind(&self) -> JvmFunctionKind {
+        self.kind
+    }
+
+    pub(crate) fn accepts_constants(&self) -> bool {
+        self.constants
+    }
+
+    pub(crate) fn computes_return_field(&self) -> bool {
+        self.return_field
+    }
+
+    pub(crate) fn invoke_java(&self, array: ArrayData) -> Result<Arc<dyn Array>> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let (input_array, input_schema) = to_ffi(&array)?;
+
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
+
+        // number of failed rows is returned by each invocation,
+        // as the same instance may be invoked concurrently
+        let (result, failures) = unsafe {
+            call_compute_method(
+                &mut env,
+                &self.target_object_global_ref,
+                self.target_method_id,
+                &[ptr_input_schema, ptr_input_array],
+                self.return_type.as_ref(),
+            )?
+        };
+
+        if self.error_policy == ErrorPolicy::Skip && failures > 0 {
+            let total = self.failures.fetch_add(failures, Ordering::Relaxed) + failures;
+            log::warn!("java function failed for {failures} rows, {total} rows in total");
+        }
+
+        Ok(result)
+    }
+
+    /// Same function (instance), invoked using given method.
+    ///
+    /// Used when return type is known only when function is
+    /// invoked, and it differs from declared return type.
+    pub(crate) fn with_method(&self, method: &JvmMethod) -> Result<JvmFunction> {
+        let mut env = _attach_tread(&self.jvm)?;
+        let clazz = env.get_object_class(&self.target_object_global_ref)?;
+
+        let method_id = env
+            .get_method_id(&clazz, &method.name, method.signature())
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        Ok(JvmFunction {
+            target_method_id: method_id,
+            target_object_global_ref: self.target_object_global_ref.clone(),
+            return_type: method.return_type.clone(),
+            kind: self.kind,
+            strict: self.strict,
+            constants: self.constants,
+            return_field: self.return_field,
+            error_policy: self.error_policy,
+            failures: self.failures.clone(),
+            jvm: self.jvm.clone(),
+        })
+    }
+
+    /// Return field computed by java from argument types and literal
+    /// arguments (single row struct, non literal arguments are null).
+    ///
+    /// Returns `None` if java does not compute return field
+    /// (`AdhesiveFunction.returnField`)
+    pub(crate) fn return_field_java(&self, arguments: &ArrayData) -> Result<Option<Field>> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let (input_array, input_schema) = to_ffi(arguments)?;
+
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64);
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64);
+
+        let mut result_schema = FFI_ArrowSchema::empty();
+        let ptr_result_schema = JValue::from(addr_of_mut!(result_schema) as i64);
+
+        let computed = env
+            .call_method(
+                &self.target_object_global_ref,
+                "exportReturnField",
+                "(JJJ)Z",
+                &[ptr_input_schema, ptr_input_array, ptr_result_schema],
+            )
+            .map_err(|e| jni_error(&mut env, e))?
+            .z()?;
+
+        if computed {
+            Ok(Some(Field::try_from(&result_schema)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn create_arrow_data(dtypes: &[DataType], arrays: &[ArrayRef]) -> Result<ArrayData> {
+        let len = arrays.first().map(|a| a.len()).unwrap_or_default();
+
+        Self::create_arrow_data_with_len(dtypes, arrays, len)
+    }
+
+    /// Struct of given number of rows, with an array per argument.
+    ///
+    /// Number of rows is given explicitly, as function
+    /// may have no arguments.
+    pub(crate) fn create_arrow_data_with_len(
+        dtypes: &[DataType],
+        arrays: &[ArrayRef],
+        len: usize,
+    ) -> Result<ArrayData> {
+        if dtypes.len() != arrays.len() {
+            Err(ArrowError::InvalidArgumentError(format!(
+                "expected {} arguments, got {}",
+                dtypes.len(),
+                arrays.len()
+            )))?
+        }
+
+        // child field is nullable only if it contains nulls,
+        // otherwise struct validation would reject it
+        let fields = dtypes
+            .iter()
+            .zip(arrays)
+            .enumerate()
+            .map(|(c, (t, a))| Field::new(format!("_c{}", c), t.clone(), a.is_nullable()))
+            .collect::<Vec<_>>();
+
+        if let Some(a) = arrays.iter().find(|a| a.len() != len) {
+            Err(ArrowError::InvalidArgumentError(format!(
+                "expected arguments of {} rows, got {}",
+                len,
+                a.len()
+            )))?
+        }
+
+        let fields = Fields::from(fields);
+
+        let array = arrays.iter().fold(
+            ArrayDataBuilder::new(DataType::Struct(fields)).len(len),
+            |b, a| b.add_child_data(a.to_data()),
+        );
+
+        Ok(array.build()?)
+    }
+}
+
+/// Accumulator of java aggregate function,
+/// backed by a new instance of function class
+#[derive(Debug)]
+pub(crate) struct JvmAccumulator {
+    update_method_id: JMethodID,
+    merge_method_id: JMethodID,
+    state_method_id: JMethodID,
+    evaluate_method_id: JMethodID,
+    target_object_global_ref: GlobalRef,
+    return_type: DataType,
+    jvm: Arc<JavaVM>,
+}
+
+impl JvmAccumulator {
+    pub(crate) fn try_new(class: &JvmClass, return_type: &DataType) -> Result<Self> {
+        log::debug!("create accumulator for class: [{}]", class.name);
+        let mut env = _attach_tread(&class.jvm)?;
+        let clazz = <&JClass>::from(class.class_global_ref.as_obj());
+
+        let update_method_id = env
+            .get_method_id(clazz, "updateBatch", "(JJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+        let merge_method_id = env
+            .get_method_id(clazz, "mergeBatch", "(JJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+        let state_method_id = env
+            .get_method_id(clazz, "exportState", "(JJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+        let evaluate_method_id = env
+            .get_method_id(clazz, "exportResult", "(JJJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        let new_object_instance = env
+            .new_object(clazz, "()V", &[])
+            .map_err(|e| jni_error(&mut env, e))?;
+        let object_global_ref = env.new_global_ref(new_object_instance)?;
+
+        Ok(Self {
+            update_method_id,
+            merge_method_id,
+            state_method_id,
+            evaluate_method_id,
+            target_object_global_ref: object_global_ref,
+            return_type: return_type.clone(),
+            jvm: class.jvm.clone(),
+        })
+    }
+
+    /// adds rows (function arguments) to accumulator
+    pub(crate) fn update_java(&self, array: ArrayData) -> Result<()> {
+        self.consume(self.update_method_id, array)
+    }
+
+    /// merges states of other accumulators,
+    /// (struct with single binary child)
+    pub(crate) fn merge_java(&self, array: ArrayData) -> Result<()> {
+        self.consume(self.merge_method_id, array)
+    }
+
+    /// exports accumulator state as single binary value
+    pub(crate) fn state_java(&self) -> Result<ArrayRef> {
+        self.produce(self.state_method_id, None)
+    }
+
+    /// exports final result as single value of function return type
+    pub(crate) fn evaluate_java(&self) -> Result<ArrayRef> {
+        self.produce(self.evaluate_method_id, Some(&self.return_type))
+    }
+
+    fn consume(&self, method_id: JMethodID, array: ArrayData) -> Result<()> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let (input_array, input_schema) = to_ffi(&array)?;
+
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
+
+        unsafe {
+            call_void_method(
+                &mut env,
+                &self.target_object_global_ref,
+                method_id,
+                &[ptr_input_schema, ptr_input_array],
+            )
+        }
+    }
+
+    fn produce(&self, method_id: JMethodID, return_type: Option<&DataType>) -> Result<ArrayRef> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        unsafe {
+            call_exporting_method(
+                &mut env,
+                &self.target_object_global_ref,
+                method_id,
+                &[],
+                return_type,
+            )
+        }
+    }
+}
+
+/// Partition evaluator of java window function,
+/// backed by a new instance of function class
+#[derive(Debug)]
+pub(crate) struct JvmPartitionEvaluator {
+    evaluate_all_method_id: JMethodID,
+    set_partition_method_id: JMethodID,
+    evaluate_method_id: JMethodID,
+    target_object_global_ref: GlobalRef,
+    return_type: DataType,
+    /// is java evaluated for each row window frame
+    /// or once for whole partition
+    uses_window_frame: bool,
+    jvm: Arc<JavaVM>,
+}
+
+impl JvmPartitionEvaluator {
+    pub(crate) fn try_new(class: &JvmClass, return_type: &DataType) -> Result<Self> {
+        log::debug!("create partition evaluator for class: [{}]", class.name);
+        let mut env = _attach_tread(&class.jvm)?;
+        let clazz = <&JClass>::from(class.class_global_ref.as_obj());
+
+        let evaluate_all_method_id = env
+            .get_method_id(clazz, "evaluateAllBatch", "(JJJJJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+        let set_partition_method_id = env
+            .get_method_id(clazz, "setPartition", "(JJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+        let evaluate_method_id = env
+            .get_method_id(clazz, "evaluateBatch", "(JJJJJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        let new_object_instance = env
+            .new_object(clazz, "()V", &[])
+            .map_err(|e| jni_error(&mut env, e))?;
+        let uses_window_frame = env
+            .call_method(&new_object_instance, "usesWindowFrame", "()Z", &[])
+            .map_err(|e| jni_error(&mut env, e))?
+            .z()?;
+        let object_global_ref = env.new_global_ref(new_object_instance)?;
+
+        Ok(Self {
+            evaluate_all_method_id,
+            set_partition_method_id,
+            evaluate_method_id,
+            target_object_global_ref: object_global_ref,
+            return_type: return_type.clone(),
+            uses_window_frame,
+            jvm: class.jvm.clone(),
+        })
+    }
+
+    pub(crate) fn uses_window_frame(&self) -> bool {
+        self.uses_window_frame
+    }
+
+    /// evaluates whole partition, returns value for each partition row
+    pub(crate) fn evaluate_all_java(&self, array: ArrayData) -> Result<ArrayRef> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let (input_array, input_schema) = to_ffi(&array)?;
+
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
+
+        unsafe {
+            call_exporting_method(
+                &mut env,
+                &self.target_object_global_ref,
+                self.evaluate_all_method_id,
+                &[ptr_input_schema, ptr_input_array],
+                Some(&self.return_type),
+            )
+        }
+    }
+
+    /// sets partition evaluated by window frames,
+    /// partition is kept by java until next one is set
+    pub(crate) fn set_partition_java(&self, array: ArrayData) -> Result<()> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let (input_array, input_schema) = to_ffi(&array)?;
+
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
+
+        unsafe {
+            call_void_method(
+                &mut env,
+                &self.target_object_global_ref,
+                self.set_partition_method_id,
+                &[ptr_input_schema, ptr_input_array],
+            )
+        }
+    }
+
+    /// evaluates window frame `[start, end)` of partition
+    /// set by [Self::set_partition_java], returns single value
+    pub(crate) fn evaluate_java(&self, start: usize, end: usize) -> Result<ArrayRef> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let start = JValue::from(start as i64).as_jni();
+        let end = JValue::from(end as i64).as_jni();
+
+        unsafe {
+            call_exporting_method(
+                &mut env,
+                &self.target_object_global_ref,
+                self.evaluate_method_id,
+                &[start, end],
+                Some(&self.return_type),
+            )
+        }
+    }
+}
+
+impl Drop for JvmPartitionEvaluator {
+    /// partition kept by java references rust memory,
+    /// so it is released with evaluator
+    fn drop(&mut self) {
+        let Ok(mut env) = _attach_tread(&self.jvm) else {
+            return;
+        };
 
-/// type of base class all function must extend
-static BASE_CLASS_TYPE: &str = "com/github/milenkovicm/adhesive/Adhesive";
-/// java compiler class implementation
-static COMPILER_CLASS_NAME: &str = "com/github/milenkovicm/adhesive/Compiler";
-/// compiler method
-static COMPILER_METHOD_NAME: &str = "compile";
+        if let Err(e) = env
+            .call_method(&self.target_object_global_ref, "close", "()V", &[])
+            .map_err(|e| jni_error(&mut env, e))
+        {
+            log::warn!("failed to close partition evaluator: {e}");
+        }
+    }
+}
 
+/// Source of java table function rows,
+/// backed by a new instance of function class
 #[derive(Debug)]
-pub struct JvmFunctionFactory {
-    // TODO: we should start VM lazily
-    //      when first function is created
+pub(crate) struct JvmTableSource {
+    schema_method_id: JMethodID,
+    stream_method_id: JMethodID,
+    target_object_global_ref: GlobalRef,
     jvm: Arc<JavaVM>,
 }
 
-impl JvmFunctionFactory {
-    pub fn new_with_jar(jar_path: &str) -> Result<Self> {
-        let jvm_args = InitArgsBuilder::new()
-            .version(JNIVersion::V8)
-            // this guy complains a lot
-            // we shut him down for now
-            //.option("-Xcheck:jni")
-            // TODO: Check if file exist
-            .option("--add-opens=java.base/java.nio=ALL-UNNAMED")
-            .option(format!("-Djava.class.path={}", jar_path))
-            .build()?;
+impl JvmTableSource {
+    pub(crate) fn try_new(class: &JvmClass) -> Result<Self> {
+        log::debug!("create table source for class: [{}]", class.name);
+        let mut env = _attach_tread(&class.jvm)?;
+        let clazz = <&JClass>::from(class.class_global_ref.as_obj());
+
+        let schema_method_id = env
+            .get_method_id(clazz, "exportSchema", "(JJJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+        let stream_method_id = env
+            .get_method_id(clazz, "exportStream", "(JJJ)V")
+            .map_err(|e| jni_error(&mut env, e))?;
+
+        let new_object_instance = env
+            .new_object(clazz, "()V", &[])
+            .map_err(|e| jni_error(&mut env, e))?;
+        let object_global_ref = env.new_global_ref(new_object_instance)?;
 
-        Self::new_from_args(jvm_args)
+        Ok(Self {
+            schema_method_id,
+            stream_method_id,
+            target_object_global_ref: object_global_ref,
+            jvm: class.jvm.clone(),
+        })
     }
 
-    pub fn new_from_args(jvm_args: InitArgs) -> Result<Self> {
-        let jvm = JVM.get_or_init(|| Arc::new(JavaVM::new(jvm_args).expect("error to create jvm")));
-        Ok(Self { jvm: jvm.clone() })
+    /// schema of rows produced for given arguments (single row struct)
+    pub(crate) fn schema_java(&self, arguments: &ArrayData) -> Result<SchemaRef> {
+        let mut env = _attach_tread(&self.jvm)?;
+
+        let (input_array, input_schema) = to_ffi(arguments)?;
+
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
+
+        let mut result_schema = FFI_ArrowSchema::empty();
+        let ptr_return_schema = JValue::from(addr_of_mut!(result_schema) as i64).as_jni();
+
+        unsafe {
+            call_void_method(
+                &mut env,
+                &self.target_object_global_ref,
+                self.schema_method_id,
+                &[ptr_input_schema, ptr_input_array, ptr_return_schema],
+            )?;
+        }
+
+        Ok(Arc::new(Schema::try_from(&result_schema)?))
     }
 
-    pub(crate) fn create_function(
-        &self,
-        class_name: &str,
-        method_name: &str,
-    ) -> Result<JvmFunction> {
-        log::info!(
-            "create function for class: [{}] and method: [{}]",
-            class_name,
-            method_name
-        );
+    /// opens stream of rows produced for given arguments (single row struct)
+    ///
+    /// stream calls back to java when batches are read,
+    /// reading thread will be attached by java if needed
+    pub(crate) fn stream_java(&self, arguments: &ArrayData) -> Result<ArrowArrayStreamReader> {
         let mut env = _attach_tread(&self.jvm)?;
 
-        let class_name = class_name.replace('.', "/");
-        let clazz = env.find_class(&class_name)?;
+        let (input_array, input_schema) = to_ffi(arguments)?;
 
-        // TODO: check if class is instance of
-        // env.is_instance_of(object, class)
-        // let subclass = env.get_superclass(&clazz)??;
+        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
+        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
 
-        let method_id = env.get_method_id(&clazz, method_name, "(JJJJ)V")?;
+        let mut result_stream = FFI_ArrowArrayStream::empty();
+        let ptr_return_stream = JValue::from(addr_of_mut!(result_stream) as i64).as_jni();
 
-        // we use default constructor to create this class
-        //
-        // an alternative was to let user define singleton `INSTANCE`.
-        // for now we will allocate new object and cache global reference.
-        //
-        // alternative was to allocate instance without calling constructor,
-        // which we avoided as user might want to use constructor to init class
-        // let new_object_instance = env.alloc_object(&clazz)?;
+        unsafe {
+            call_void_method(
+                &mut env,
+                &self.target_object_global_ref,
+                self.stream_method_id,
+                &[ptr_input_schema, ptr_input_array, ptr_return_stream],
+            )?;
+        }
 
-        let new_object_instance = env.new_object(&clazz, "()V", &[])?;
+        Ok(ArrowArrayStreamReader::try_new(result_stream)?)
+    }
+}
 
-        // The JNI divides object references used by the native code into two
-        // categories: local and global references. Local references are valid
-        // for the duration of a native method call, and are automatically freed after
-        // the native method returns. Global references remain valid until they are explicitly freed.
-        //
-        // https://docs.oracle.com/javase/8/docs/technotes/guides/jni/spec/design.html
+/// calls java method which exports its result as an arrow array.
+///
+/// Method is called with given arguments followed by addresses of
+/// result schema and array, and address of exported return type
+/// schema if return type is provided.
+///
+/// # Safety
+///
+/// method and arguments have to match target object
+unsafe fn call_exporting_method(
+    env: &mut JNIEnv,
+    target: &GlobalRef,
+    method_id: JMethodID,
+    args: &[jvalue],
+    return_type: Option<&DataType>,
+) -> Result<ArrayRef> {
+    export_result(env, args, return_type, |env, args| {
+        call_void_method(env, target, method_id, args)
+    })
+}
 
-        let object_global_ref = env.new_global_ref(new_object_instance)?;
+/// calls java function entry point, which exports its result same as
+/// [call_exporting_method], and returns number of rows it failed for
+///
+/// # Safety
+///
+/// method and arguments have to match target object
+unsafe fn call_compute_method(
+    env: &mut JNIEnv,
+    target: &GlobalRef,
+    method_id: JMethodID,
+    args: &[jvalue],
+    return_type: Option<&DataType>,
+) -> Result<(ArrayRef, u64)> {
+    let mut failures = 0;
+    let result = export_result(env, args, return_type, |env, args| {
+        failures = env
+            .call_method_unchecked(
+                target,
+                method_id,
+                ReturnType::Primitive(jni::signature::Primitive::Long),
+                args,
+            )
+            .map_err(|e| jni_error(env, e))?
+            .j()? as u64;
 
-        Ok(JvmFunction {
-            target_method_id: method_id,
-            target_object_global_ref: object_global_ref,
-            jvm: self.jvm.clone(),
-        })
+        Ok(())
+    })?;
+
+    Ok((result, failures))
+}
+
+/// adds addresses of result (and return type, if any) to arguments,
+/// and imports result exported by `call` made with them
+///
+/// # Safety
+///
+/// call has to export result to given addresses
+unsafe fn export_result(
+    env: &mut JNIEnv,
+    args: &[jvalue],
+    return_type: Option<&DataType>,
+    call: impl FnOnce(&mut JNIEnv, &[jvalue]) -> Result<()>,
+) -> Result<ArrayRef> {
+    let mut result_schema = FFI_ArrowSchema::empty();
+    let mut result_array = FFI_ArrowArray::empty();
+
+    let ptr_return_array = JValue::from(addr_of_mut!(result_array) as i64).as_jni();
+    let ptr_return_schema = JValue::from(addr_of_mut!(result_schema) as i64).as_jni();
+
+    let mut args = args.to_vec();
+    args.push(ptr_return_schema);
+    args.push(ptr_return_array);
+
+    // java side takes ownership of the schema (releases it),
+    // in case it does not, schema will be released on drop
+    let mut return_type_schema = match return_type {
+        Some(return_type) => Some(FFI_ArrowSchema::try_from(Field::new(
+            "result",
+            return_type.clone(),
+            true,
+        ))?),
+        None => None,
+    };
+
+    if let Some(return_type_schema) = return_type_schema.as_mut() {
+        args.push(JValue::from(addr_of_mut!(*return_type_schema) as i64).as_jni());
     }
 
-    pub(crate) fn compile_create_function(
-        &self,
-        java_code: &str,
-        method_name: &str,
-    ) -> Result<JvmFunction> {
-        let mut env = _attach_tread(&self.jvm)?;
+    call(env, &args)?;
 
-        let compiler_clazz = env.find_class(COMPILER_CLASS_NAME)?;
-        let compiler_signature =
-            format!("(Ljava/lang/String;Ljava/lang/String;)L{BASE_CLASS_TYPE};");
+    let result_array = from_ffi(result_array, &result_schema)?;
+    Ok(make_array(result_array))
+}
 
-        let compile_method_id =
-            env.get_method_id(&compiler_clazz, COMPILER_METHOD_NAME, compiler_signature)?;
+/// calls java method returning `void`
+///
+/// # Safety
+///
+/// method and arguments have to match target object
+unsafe fn call_void_method(
+    env: &mut JNIEnv,
+    target: &GlobalRef,
+    method_id: JMethodID,
+    args: &[jvalue],
+) -> Result<()> {
+    env.call_method_unchecked(
+        target,
+        method_id,
+        ReturnType::Primitive(jni::signature::Primitive::Void),
+        args,
+    )
+    .map_err(|e| jni_error(env, e))?;
 
-        // compiler is a singleton
-        // look for static filed called `INSTANCE`
-        let compiler_instance = env.get_static_field(
-            COMPILER_CLASS_NAME,
-            "INSTANCE",
-            format!("L{COMPILER_CLASS_NAME};"),
-        )?;
+    Ok(())
+}
 
-        let (java_code, fqn) = crate::util::update_java_code(java_code)?;
+/// error policy declared by function instance, if any
+fn declared_error_policy(env: &mut JNIEnv, instance: &JObject) -> Result<Option<ErrorPolicy>> {
+    let error_policy = env
+        .call_method(
+            instance,
+            "errorPolicy",
+            format!("()L{ERROR_POLICY_CLASS_NAME};"),
+            &[],
+        )
+        .map_err(|e| jni_error(env, e))?
+        .l()?;
 
-        let new_class_name = env.new_string(fqn)?;
-        let new_class_definition = env.new_string(java_code)?;
-        let new_class_name = JValue::Object(&new_class_name).as_jni();
-        let new_class_definition = JValue::Object(&new_class_definition).as_jni();
+    if error_policy.is_null() {
+        return Ok(None);
+    }
 
-        unsafe {
-            let result = env
-                .call_method_unchecked(
-                    &compiler_instance.l()?,
-                    compile_method_id,
-                    ReturnType::Object,
-                    &[new_class_name, new_class_definition],
-                )?
-                .l();
-
-            if has_exception_occurred(&mut env)? {
-                Err(JvmFunctionError::JvmException(
-                    "no handling for exception messages yet".into(),
-                ))
-            } else {
-                let new_instance = result?;
-                let clazz = env.get_object_class(&new_instance)?;
-                let target_method_id = env.get_method_id(&clazz, method_name, "(JJJJ)V")?;
-                let target_object_global_ref = env.new_global_ref(new_instance)?;
-
-                Ok(JvmFunction {
-                    target_method_id,
-                    target_object_global_ref,
-                    jvm: self.jvm.clone(),
-                })
-            }
-        }
+    let name = env
+        .call_method(&error_policy, "name", "()Ljava/lang/String;", &[])
+        .map_err(|e| jni_error(env, e))?
+        .l()?;
+    let name = java_string(env, name)?;
+
+    // java enum has the same values
+    Ok(name.parse().ok())
+}
+
+/// sets what function instance should do when it fails for a row
+fn set_error_policy(env: &mut JNIEnv, instance: &JObject, error_policy: ErrorPolicy) -> Result<()> {
+    let error_policy = env.new_string(error_policy.name())?;
+    env.call_method(
+        instance,
+        "setErrorPolicy",
+        "(Ljava/lang/String;)V",
+        &[JValue::Object(&error_policy)],
+    )
+    .map_err(|e| jni_error(env, e))?;
+
+    Ok(())
+}
+
+/// checks if function instance declared itself as strict
+/// (`RETURNS NULL ON NULL INPUT`)
+fn is_strict(env: &mut JNIEnv, instance: &JObject) -> Result<bool> {
+    Ok(env
+        .call_method(instance, "isStrict", "()Z", &[])
+        .map_err(|e| jni_error(env, e))?
+        .z()?)
+}
+
+/// checks if (vectorized) function instance accepts constant
+/// arguments as run end encoded vectors
+fn accepts_constant_arguments(env: &mut JNIEnv, instance: &JObject) -> Result<bool> {
+    Ok(env
+        .call_method(instance, "acceptsConstantArguments", "()Z", &[])
+        .map_err(|e| jni_error(env, e))?
+        .z()?)
+}
+
+/// checks if function instance computes return field
+/// from arguments, rather than using declared return type
+fn computes_return_field(env: &mut JNIEnv, instance: &JObject) -> Result<bool> {
+    Ok(env
+        .call_method(instance, "computesReturnField", "()Z", &[])
+        .map_err(|e| jni_error(env, e))?
+        .z()?)
+}
+
+/// max number of exception causes to be reported,
+/// cause chain may be (indirectly) cyclic
+const MAX_EXCEPTION_CAUSES: usize = 16;
+
+/// converts jni error to [JvmFunctionError].
+///
+/// if error is caused by java exception, exception is cleared
+/// and converted to [JvmFunctionError::JvmException]
+fn jni_error(env: &mut JNIEnv, error: jni::errors::Error) -> JvmFunctionError {
+    match error {
+        jni::errors::Error::JavaException => match take_exception(env) {
+            Ok(Some(exception)) => exception,
+            Ok(None) => error.into(),
+            Err(e) => e,
+        },
+        error => error.into(),
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct JvmFunction {
-    target_method_id: JMethodID,
+/// takes pending exception (if any), exception is cleared,
+/// as JVM can't be called while exception is pending
+fn take_exception(env: &mut JNIEnv) -> Result<Option<JvmFunctionError>> {
+    if !env.exception_check()? {
+        return Ok(None);
+    }
 
-    // TODO: WARN  jni::wrapper::objects::global_ref] Dropping a GlobalRef in a detached thread.
-    // Fix your code if this message appears frequently (see the GlobalRef docs).
-    //
-    // checking current Drop impl for GlobalRef it would do same thing I'll do
-    target_object_global_ref: GlobalRef,
-    jvm: Arc<JavaVM>,
+    let throwable = env.exception_occurred()?;
+    env.exception_clear()?;
+
+    Ok(Some(java_exception(env, &throwable)))
 }
 
-impl JvmFunction {
-    pub(crate) fn invoke_java(&self, array: ArrayData) -> Result<Arc<dyn Array>> {
-        let mut env = _attach_tread(&self.jvm)?;
+/// converts (cleared) exception to [JvmFunctionError::JvmException].
+///
+/// Describing exception calls java, exceptions thrown by those calls
+/// are cleared and ignored, so original exception is not lost
+fn java_exception(env: &mut JNIEnv, throwable: &JObject) -> JvmFunctionError {
+    let exception = describe_throwable(env, throwable);
 
-        let (input_array, input_schema) = to_ffi(&array)?;
+    let mut causes = vec![];
+    let mut cause = get_cause(env, throwable);
+    while let Some(current) = cause.filter(|_| causes.len() < MAX_EXCEPTION_CAUSES) {
+        causes.push(describe_throwable(env, &current));
+        cause = get_cause(env, &current);
+    }
 
-        let ptr_input_array = JValue::from(addr_of!(input_array) as i64).as_jni();
-        let ptr_input_schema = JValue::from(addr_of!(input_schema) as i64).as_jni();
+    // exception class is better than nothing
+    let stack_trace = stack_trace(env, throwable).unwrap_or_else(|| exception.to_string());
+    log::debug!("java exception thrown: {}", stack_trace);
 
-        let mut result_schema = FFI_ArrowSchema::empty();
-        let mut result_array = FFI_ArrowArray::empty();
+    JvmFunctionError::JvmException {
+        exception,
+        causes,
+        stack_trace,
+    }
+}
 
-        let ptr_return_array = JValue::from(addr_of_mut!(result_array) as i64).as_jni();
-        let ptr_return_schema = JValue::from(addr_of_mut!(result_schema) as i64).as_jni();
+/// reads diagnostics of compilation exception,
+/// line numbers are adjusted to refer to function definition
+fn compilation_diagnostics(env: &mut JNIEnv, throwable: &JObject) -> Result<Vec<JavaDiagnostic>> {
+    let diagnostics = env
+        .call_method(throwable, "getDiagnostics", "()Ljava/util/List;", &[])
+        .map_err(|e| jni_error(env, e))?
+        .l()?;
+    // arrays are easier to handle over JNI
+    let diagnostics = env
+        .call_method(&diagnostics, "toArray", "()[Ljava/lang/Object;", &[])
+        .map_err(|e| jni_error(env, e))?
+        .l()?;
+    let diagnostics = JObjectArray::from(diagnostics);
 
-        unsafe {
-            let _result = env.call_method_unchecked(
-                &self.target_object_global_ref,
-                self.target_method_id,
-                ReturnType::Primitive(jni::signature::Primitive::Void),
-                &[
-                    ptr_input_schema,
-                    ptr_input_array,
-                    ptr_return_schema,
-                    ptr_return_array,
-                ],
-            );
+    let len = env.get_array_length(&diagnostics)?;
+    let mut result = Vec::with_capacity(len as usize);
 
-            if has_exception_occurred(&mut env)? {
-                Err(JvmFunctionError::JvmException(
-                    "no handling for exception messages yet".into(),
-                ))
+    for i in 0..len {
+        let diagnostic = env
+            .get_object_array_element(&diagnostics, i)
+            .map_err(|e| jni_error(env, e))?;
+
+        let severity = env
+            .call_method(&diagnostic, "severity", "()Ljava/lang/String;", &[])
+            .map_err(|e| jni_error(env, e))?
+            .l()?;
+        let line = env
+            .call_method(&diagnostic, "line", "()J", &[])
+            .map_err(|e| jni_error(env, e))?
+            .j()?;
+        let column = env
+            .call_method(&diagnostic, "column", "()J", &[])
+            .map_err(|e| jni_error(env, e))?
+            .j()?;
+        let message = env
+            .call_method(&diagnostic, "message", "()Ljava/lang/String;", &[])
+            .map_err(|e| jni_error(env, e))?
+            .l()?;
+
+        result.push(JavaDiagnostic {
+            severity: java_string(env, severity)?,
+            // unknown line is reported as -1
+            line: if line > crate::util::GENERATED_LINES {
+                line - crate::util::GENERATED_LINES
             } else {
-                let result_array = from_ffi(result_array, &result_schema)?;
-                Ok(make_array(result_array))
-            }
-        }
+                line
+            },
+            column,
+            message: java_string(env, message)?,
+        });
     }
 
-    pub(crate) fn create_arrow_data(dtypes: &[DataType], arrays: &[ArrayRef]) -> Result<ArrayData> {
-        // TODO: assert len dtypes and arrays
-        let fields = dtypes
-            .iter()
-            .enumerate()
-            .map(|(c, t)| Field::new(format!("_c{}", c), t.clone(), false))
-            .collect::<Vec<_>>();
+    Ok(result)
+}
 
-        let fields = Fields::from(fields);
+fn describe_throwable(env: &mut JNIEnv, throwable: &JObject) -> JavaThrowable {
+    let class = class_name(env, throwable).unwrap_or_else(|| "java.lang.Throwable".to_string());
 
-        let array = arrays
-            .iter()
-            .fold(ArrayDataBuilder::new(DataType::Struct(fields)), |b, a| {
-                b.add_child_data(a.to_data())
-            });
+    let message = env
+        .call_method(throwable, "getMessage", "()Ljava/lang/String;", &[])
+        .and_then(|m| m.l());
+    let message = ignore_exception(env, message).and_then(|m| describe_string(env, m));
 
-        Ok(array.build()?)
+    JavaThrowable { class, message }
+}
+
+fn class_name(env: &mut JNIEnv, object: &JObject) -> Option<String> {
+    let class = env.get_object_class(object);
+    let class = ignore_exception(env, class)?;
+
+    let name = env
+        .call_method(&class, "getName", "()Ljava/lang/String;", &[])
+        .and_then(|n| n.l());
+    let name = ignore_exception(env, name)?;
+
+    describe_string(env, name)
+}
+
+fn get_cause<'local>(env: &mut JNIEnv<'local>, throwable: &JObject) -> Option<JObject<'local>> {
+    let cause = env
+        .call_method(throwable, "getCause", "()Ljava/lang/Throwable;", &[])
+        .and_then(|c| c.l());
+
+    ignore_exception(env, cause).filter(|c| !c.is_null())
+}
+
+/// stack trace as printed by `Throwable.printStackTrace`
+fn stack_trace(env: &mut JNIEnv, throwable: &JObject) -> Option<String> {
+    let writer = env.new_object("java/io/StringWriter", "()V", &[]);
+    let writer = ignore_exception(env, writer)?;
+
+    let print_writer = env.new_object(
+        "java/io/PrintWriter",
+        "(Ljava/io/Writer;)V",
+        &[JValue::Object(&writer)],
+    );
+    let print_writer = ignore_exception(env, print_writer)?;
+
+    let printed = env.call_method(
+        throwable,
+        "printStackTrace",
+        "(Ljava/io/PrintWriter;)V",
+        &[JValue::Object(&print_writer)],
+    );
+    ignore_exception(env, printed)?;
+
+    let stack_trace = env
+        .call_method(&writer, "toString", "()Ljava/lang/String;", &[])
+        .and_then(|s| s.l());
+    let stack_trace = ignore_exception(env, stack_trace)?;
+
+    describe_string(env, stack_trace)
+}
+
+/// java string, used while describing exception
+fn describe_string(env: &mut JNIEnv, string: JObject) -> Option<String> {
+    if string.is_null() {
+        return None;
     }
+
+    let string = JString::from(string);
+    let result = env.get_string(&string).map(String::from);
+
+    ignore_exception(env, result)
 }
 
-// TODO: it is not clear if we need to clear exception if we
-//       propagate it further and `JNIEnv` is closed after
-//       propagation
-fn has_exception_occurred(env: &mut JNIEnv) -> Result<bool> {
-    let result = if env.exception_check()? {
-        let _exception = env.exception_occurred()?;
-        // good for now
-        env.exception_describe()?;
-        env.exception_clear()?;
+/// result of a call made while describing exception, exception
+/// thrown by the call (if any) is cleared and ignored
+fn ignore_exception<T>(env: &mut JNIEnv, result: jni::errors::Result<T>) -> Option<T> {
+    match result {
+        Ok(result) => Some(result),
+        Err(e) => {
+            if env.exception_check().unwrap_or_default() {
+                let _ = env.exception_clear();
+            }
+            log::debug!("failed to describe java exception: {e}");
+            None
+        }
+    }
+}
 
-        true
-    } else {
-        false
-    };
+fn java_string(env: &mut JNIEnv, string: JObject) -> Result<String> {
+    let string = JString::from(string);
+    let result = env.get_string(&string)?.into();
 
     Ok(result)
 }
@@ -269,16 +1745,19 @@ fn has_exception_occurred(env: &mut JNIEnv) -> Result<bool> {
 // if attaching it temporary it makes it harder to debug (as attached thread gets new
 // name every time it is registered)
 #[inline]
-fn _attach_tread(jvm: &JavaVM) -> std::result::Result<JNIEnv, jni::errors::Error> {
+fn _attach_tread(jvm: &JavaVM) -> std::result::Result<JNIEnv<'_>, jni::errors::Error> {
     jvm.attach_current_thread_as_daemon()
 }
 #[cfg(test)]
 mod test {
 
-    use super::{JvmFunction, JvmFunctionFactory};
+    use super::{
+        parse_declared_type, signature_accepts, JvmFunction, JvmFunctionFactory, JvmMethod,
+    };
+    use crate::{ErrorPolicy, JvmConfig, JvmFunctionError};
     use datafusion::arrow::{
         array::{ArrayData, ArrayRef, Int64Array},
-        datatypes::DataType,
+        datatypes::{DataType, Field},
     };
     use std::sync::Arc;
 
@@ -287,9 +1766,11 @@ mod test {
     #[test]
     fn should_call_basic_example() -> super::Result<()> {
         let factory = JvmFunctionFactory::new_with_jar(JAR_PATH)?;
+        let class = factory.load_class("com.github.milenkovicm.adhesive.example.BasicExample")?;
         let function = factory.create_function(
-            "com.github.milenkovicm.adhesive.example.BasicExample",
-            "computeBigInt",
+            &class,
+            &JvmMethod::new("computeBigInt"),
+            ErrorPolicy::default(),
         )?;
         let array_data = create_dummy_data()?;
         let result = function.invoke_java(array_data)?;
@@ -300,31 +1781,116 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn should_start_jvm_lazily() -> super::Result<()> {
+        let factory = JvmFunctionFactory::new_with_jar(JAR_PATH)?;
+        assert!(factory.jvm.get().is_none());
+
+        factory.warm_up()?;
+        assert!(factory.jvm.get().is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_incompatible_jvm_options() -> super::Result<()> {
+        JvmFunctionFactory::new_with_jar(JAR_PATH)?.warm_up()?;
+
+        let config = JvmConfig::new()
+            .with_class_path(JAR_PATH)
+            .with_property("adhesive.test", "true");
+        let factory = JvmFunctionFactory::new_with_config(config)?;
+
+        assert!(matches!(
+            factory.warm_up(),
+            Err(JvmFunctionError::IncompatibleJvmError { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_function_class() -> super::Result<()> {
+        let factory = JvmFunctionFactory::new_with_jar(JAR_PATH)?;
+
+        let class = factory.load_class("java.lang.String");
+        assert!(
+            matches!(class, Err(JvmFunctionError::InvalidFunctionClass { class, .. }) if class == "java.lang.String")
+        );
+
+        let class = factory.load_class("com.github.milenkovicm.adhesive.Adhesive");
+        assert!(
+            matches!(class, Err(JvmFunctionError::InvalidFunctionClass { reason, .. }) if reason == "class is abstract")
+        );
+
+        Ok(())
+    }
+
     #[test]
     // TODO: do we need to handle error case like when we invoke function
     fn should_fail_to_find_class() -> super::Result<()> {
         let factory = JvmFunctionFactory::new_with_jar(JAR_PATH)?;
-        let _function = factory.create_function(
-            "com.github.milenkovicm.adhesive.example.FakeExample",
-            "computeBigInt",
-        );
+        let class = factory.load_class("com.github.milenkovicm.adhesive.example.FakeExample");
 
-        assert!(_function.is_err());
+        assert!(matches!(class, Err(JvmFunctionError::JvmException { .. })));
         Ok(())
     }
 
     #[test]
     fn should_handle_exceptions() -> super::Result<()> {
         let factory = JvmFunctionFactory::new_with_jar(JAR_PATH)?;
+        let class =
+            factory.load_class("com.github.milenkovicm.adhesive.example.FreaksOutExample")?;
         let function = factory.create_function(
-            "com.github.milenkovicm.adhesive.example.FreaksOutExample",
-            "computeBigInt",
+            &class,
+            &JvmMethod::new("computeBigInt"),
+            ErrorPolicy::default(),
         )?;
 
         let array_data = create_dummy_data()?;
         let result = function.invoke_java(array_data);
 
-        assert!(result.is_err());
+        let Err(JvmFunctionError::JvmException {
+            exception,
+            stack_trace,
+            ..
+        }) = result
+        else {
+            panic!("java exception expected, got: {:?}", result)
+        };
+        assert_eq!("java.lang.RuntimeException", exception.class);
+        assert_eq!(
+            Some("Its ok to freak out sometimes".to_string()),
+            exception.message
+        );
+        assert!(stack_trace.contains("FreaksOutExample.compute"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_return_map() -> super::Result<()> {
+        let factory = JvmFunctionFactory::new_with_jar(JAR_PATH)?;
+        let entries = Field::new_struct(
+            "entries",
+            vec![
+                Field::new("keys", DataType::Utf8, false),
+                Field::new("values", DataType::Int64, true),
+            ],
+            false,
+        );
+        let return_type = DataType::Map(Arc::new(entries), false);
+        let class = factory.load_class("com.github.milenkovicm.adhesive.example.MapExample")?;
+        let function = factory.create_function(
+            &class,
+            &JvmMethod::with_return_type("computeNested", &return_type),
+            ErrorPolicy::default(),
+        )?;
+        let array_data = create_dummy_data()?;
+        let result = function.invoke_java(array_data)?;
+
+        assert_eq!(&return_type, result.data_type());
+        assert_eq!(3, result.len());
 
         Ok(())
     }
@@ -345,7 +1911,12 @@ mod test {
             }
             "#;
 
-        let function = factory.compile_create_function(java_code, "computeBigInt")?;
+        let class = factory.compile_class(java_code)?;
+        let function = factory.create_function(
+            &class,
+            &JvmMethod::new("computeBigInt"),
+            ErrorPolicy::default(),
+        )?;
         let array_data = create_dummy_data()?;
         let result = function.invoke_java(array_data)?;
 
@@ -355,6 +1926,100 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn should_create_arrow_data_with_nulls() -> super::Result<()> {
+        let array0 = Int64Array::from(vec![Some(100), None, Some(300)]);
+        let array1 = Int64Array::from(vec![Some(1001), Some(2002), Some(3003)]);
+
+        let arrays = vec![Arc::new(array0) as ArrayRef, Arc::new(array1)];
+        let types = vec![DataType::Int64, DataType::Int64];
+
+        let array_data = JvmFunction::create_arrow_data(&types, &arrays)?;
+
+        assert_eq!(3, array_data.len());
+        let DataType::Struct(fields) = array_data.data_type() else {
+            panic!("struct expected")
+        };
+        assert!(fields[0].is_nullable());
+        assert!(!fields[1].is_nullable());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_accept_arguments_matching_signature() {
+        use datafusion::logical_expr::TypeSignature;
+
+        let variadic = TypeSignature::Variadic(vec![DataType::Int64]);
+        assert!(signature_accepts(
+            &variadic,
+            &[DataType::Int64, DataType::Int64]
+        ));
+        assert!(!signature_accepts(
+            &variadic,
+            &[DataType::Int64, DataType::Utf8]
+        ));
+        assert!(!signature_accepts(&variadic, &[]));
+
+        let uniform = TypeSignature::Uniform(2, vec![DataType::Utf8]);
+        assert!(signature_accepts(
+            &uniform,
+            &[DataType::Utf8, DataType::Utf8]
+        ));
+        assert!(!signature_accepts(&uniform, &[DataType::Utf8]));
+
+        let numeric = TypeSignature::Numeric(1);
+        assert!(signature_accepts(&numeric, &[DataType::Float64]));
+        assert!(!signature_accepts(&numeric, &[DataType::Utf8]));
+
+        assert!(signature_accepts(
+            &TypeSignature::VariadicAny,
+            &[DataType::Utf8, DataType::Int8]
+        ));
+    }
+
+    #[test]
+    fn should_parse_declared_map_type() {
+        let entries = Field::new_struct(
+            "entries",
+            vec![
+                Field::new("keys", DataType::Utf8, false),
+                Field::new("values", DataType::Decimal128(20, 2), true),
+            ],
+            false,
+        );
+
+        assert_eq!(
+            DataType::Map(Arc::new(entries), false),
+            parse_declared_type("Map(Utf8, Decimal128(20, 2))").unwrap()
+        );
+        assert_eq!(DataType::Int64, parse_declared_type("Int64").unwrap());
+        assert!(parse_declared_type("Map(Utf8)").is_err());
+    }
+
+    #[test]
+    fn should_reject_arrow_data_with_missing_arguments() {
+        let array0 = Int64Array::from(vec![Some(100), None, Some(300)]);
+
+        let arrays = vec![Arc::new(array0) as ArrayRef];
+        let types = vec![DataType::Int64, DataType::Int64];
+
+        assert!(JvmFunction::create_arrow_data(&types, &arrays).is_err());
+    }
+
+    #[test]
+    fn should_create_arrow_data_with_given_number_of_rows() -> super::Result<()> {
+        // function without arguments
+        let array_data = JvmFunction::create_arrow_data_with_len(&[], &[], 3)?;
+        assert_eq!(3, array_data.len());
+        assert!(array_data.child_data().is_empty());
+
+        let arrays = vec![Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef];
+        assert!(JvmFunction::create_arrow_data_with_len(&[DataType::Int64], &arrays, 3).is_err());
+
+        Ok(())
+    }
+
     fn create_dummy_data() -> super::Result<ArrayData> {
         let array0 = Int64Array::from(vec![Some(100), Some(200), Some(300)]);
         let array1 = Int64Array::from(vec![Some(1001), Some(2002), Some(3003)]);
@@ -362,6 +2027,6 @@ mod test {
         let arrays = vec![Arc::new(array0) as ArrayRef, Arc::new(array1)];
         let types = vec![DataType::Int64, DataType::Int64];
 
-        Ok(JvmFunction::create_arrow_data(&types, &arrays)?)
+        JvmFunction::create_arrow_data(&types, &arrays)
     }
 }
@@ -1,27 +1,136 @@
 // TODO: remove this later
 #![allow(dead_code)]
 
+pub use crate::config::{GarbageCollector, JvmConfig};
 pub use crate::jvm::JvmFunctionFactory;
+pub use crate::options::{AdhesiveOptions, ErrorPolicy};
 
+use datafusion::arrow::datatypes::DataType;
 use thiserror::Error;
+mod config;
 mod fusion;
 mod jvm;
+mod locator;
+mod options;
 mod util;
 
 #[derive(Error, Debug)]
 pub enum JvmFunctionError {
     #[error("Error starting JVM: {0}")]
     StartJvmError(#[from] jni::errors::StartJvmError),
+    #[error("JVM configuration error: {0}")]
+    JvmConfigError(String),
+    #[error("JVM library {file_name} could not be found, searched: [{}]. Set JAVA_HOME to JDK installation directory", .searched.join(", "))]
+    LibJvmNotFound {
+        file_name: String,
+        /// java homes searched, in order
+        searched: Vec<String>,
+    },
+    #[error("JVM has already been started with options {started:?}, which are not compatible with requested options {requested:?}")]
+    IncompatibleJvmError {
+        started: Vec<String>,
+        requested: Vec<String>,
+    },
     #[error("JVM error: {0}")]
     JvmFunctionError(#[from] jni::JvmError),
     #[error("JNI error: {0}")]
     JniErrors(#[from] jni::errors::Error),
     #[error("Arrow error: {0}")]
     ArrowError(#[from] datafusion::arrow::error::ArrowError),
-    #[error("JVM call throw exception: {0}")]
-    JvmException(String),
+    #[error("JVM call throw exception: {exception}{}", caused_by(.causes))]
+    JvmException {
+        exception: JavaThrowable,
+        /// exception cause chain, direct cause first
+        causes: Vec<JavaThrowable>,
+        stack_trace: String,
+    },
+    #[error("Class [{0}] not found, adhesive jar on the class path may be older than this crate")]
+    AdhesiveClassNotFound(String),
+    #[error("Invalid function class [{class}]: {reason}")]
+    InvalidFunctionClass { class: String, reason: String },
+    #[error("Function class [{class}] expects arguments ({}), got ({})", data_types(.expected), data_types(.actual))]
+    ArgumentTypeMismatch {
+        class: String,
+        expected: Vec<DataType>,
+        actual: Vec<DataType>,
+    },
+    #[error("Function class [{class}] accepts arguments ({signature}), got ({})", data_types(.actual))]
+    SignatureMismatch {
+        class: String,
+        signature: String,
+        actual: Vec<DataType>,
+    },
+    #[error("Function class [{class}] returns {expected}, got {actual}")]
+    ReturnTypeMismatch {
+        class: String,
+        expected: DataType,
+        actual: DataType,
+    },
     #[error("Java code error: {0}")]
     JavaCodeError(String),
+    #[error("Java compilation failed: {}", diagnostics(.0))]
+    JavaCompilationError(Vec<JavaDiagnostic>),
+}
+
+/// Java exception (or its cause) class name and message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaThrowable {
+    pub class: String,
+    pub message: Option<String>,
+}
+
+impl std::fmt::Display for JavaThrowable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "{}: {}", self.class, message),
+            None => write!(f, "{}", self.class),
+        }
+    }
+}
+
+/// Java compiler diagnostic, line and column refer to function definition
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JavaDiagnostic {
+    /// diagnostic kind, like `ERROR` or `WARNING`
+    pub severity: String,
+    /// line number (starting from 1), or -1 if not known
+    pub line: i64,
+    /// column number (starting from 1), or -1 if not known
+    pub column: i64,
+    pub message: String,
+}
+
+impl std::fmt::Display for JavaDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}: {}",
+            self.severity, self.line, self.column, self.message
+        )
+    }
+}
+
+fn data_types(data_types: &[DataType]) -> String {
+    data_types
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn diagnostics(diagnostics: &[JavaDiagnostic]) -> String {
+    diagnostics
+        .iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+fn caused_by(causes: &[JavaThrowable]) -> String {
+    causes
+        .iter()
+        .map(|cause| format!(", caused by: {cause}"))
+        .collect()
 }
 
 #[cfg(test)]
@@ -0,0 +1,204 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{jvm::Result, JvmFunctionError};
+
+/// common location of JVM installations on linux
+const JVM_INSTALL_DIR: &str = "/usr/lib/jvm";
+
+#[cfg(target_os = "windows")]
+const LIBJVM_FILE_NAME: &str = "jvm.dll";
+#[cfg(target_os = "macos")]
+const LIBJVM_FILE_NAME: &str = "libjvm.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const LIBJVM_FILE_NAME: &str = "libjvm.so";
+
+/// libjvm location, relative to java home
+const LIBJVM_DIRS: &[&str] = &[
+    "lib/server",
+    "lib/client",
+    "lib",
+    "bin/server",
+    "bin/client",
+    "jre/lib/server",
+    "jre/lib/amd64/server",
+    "jre/lib/aarch64/server",
+    "jre/bin/server",
+];
+
+/// Finds JVM shared library (`libjvm.so`, `libjvm.dylib` or `jvm.dll`),
+/// looking at (in order):
+///
+/// - `JAVA_HOME`
+/// - `java.home` reported by `java -XshowSettings:properties`,
+///   for `java` from `JAVA_HOME` and `java` from `PATH`
+/// - installations in `/usr/lib/jvm`
+///
+/// Search stops at first location containing libjvm, `java` is run
+/// only if libjvm has not been found in locations before it.
+pub(crate) fn locate_libjvm() -> Result<PathBuf> {
+    let java_home = std::env::var_os("JAVA_HOME").map(PathBuf::from);
+
+    // JAVA_HOME may be stale, so java from PATH is tried as well
+    let javas = java_home
+        .iter()
+        .map(|h| h.join("bin").join("java"))
+        .chain(std::iter::once(PathBuf::from("java")))
+        .map(|java| {
+            java_home_from_java(&java)
+                .ok_or_else(|| format!("{} (java.home not reported)", java.display()))
+        });
+
+    let installed = std::iter::once_with(|| installed_java_homes(Path::new(JVM_INSTALL_DIR)))
+        .flatten()
+        .map(Ok);
+
+    find_libjvm(
+        java_home
+            .clone()
+            .map(Ok)
+            .into_iter()
+            .chain(javas)
+            .chain(installed),
+    )
+}
+
+/// Returns libjvm found in first java home containing it.
+///
+/// Java homes are evaluated lazily, as they are searched. Locations
+/// which could not be resolved to java home are reported as errors,
+/// they are listed as searched.
+fn find_libjvm(
+    java_homes: impl IntoIterator<Item = std::result::Result<PathBuf, String>>,
+) -> Result<PathBuf> {
+    let mut visited = vec![];
+    let mut searched = vec![];
+
+    for java_home in java_homes {
+        let java_home = match java_home {
+            Ok(java_home) => java_home,
+            Err(location) => {
+                searched.push(location);
+                continue;
+            }
+        };
+
+        // the same java home may be reported more than once
+        if visited.contains(&java_home) {
+            continue;
+        }
+
+        for dir in LIBJVM_DIRS {
+            let path = java_home.join(dir).join(LIBJVM_FILE_NAME);
+            if path.is_file() {
+                log::debug!("found libjvm: {}", path.display());
+                return Ok(path);
+            }
+        }
+        searched.push(java_home.display().to_string());
+        visited.push(java_home);
+    }
+
+    Err(JvmFunctionError::LibJvmNotFound {
+        file_name: LIBJVM_FILE_NAME.to_string(),
+        searched,
+    })
+}
+
+/// `java.home` reported by given `java` executable
+fn java_home_from_java(java: &Path) -> Option<PathBuf> {
+    // settings are printed to stderr
+    let output = Command::new(java)
+        .args(["-XshowSettings:properties", "-version"])
+        .output()
+        .map_err(|e| log::debug!("can't run {}: {}", java.display(), e))
+        .ok()?;
+
+    java_home_from_settings(&String::from_utf8_lossy(&output.stderr))
+}
+
+/// parses `java.home` from `java -XshowSettings:properties` output
+fn java_home_from_settings(settings: &str) -> Option<PathBuf> {
+    settings.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        if key.trim() == "java.home" {
+            Some(PathBuf::from(value.trim()))
+        } else {
+            None
+        }
+    })
+}
+
+/// java homes installed in given directory, `default-java` first
+fn installed_java_homes(dir: &Path) -> Vec<PathBuf> {
+    let mut java_homes = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect::<Vec<_>>(),
+        Err(_) => vec![],
+    };
+
+    java_homes.sort_by_key(|path| (!path.ends_with("default-java"), path.clone()));
+    java_homes
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::{find_libjvm, java_home_from_settings, LIBJVM_FILE_NAME};
+    use crate::JvmFunctionError;
+
+    #[test]
+    fn should_parse_java_home_from_settings() {
+        let settings = "Property settings:\n    file.encoding = UTF-8\n    java.home = /usr/lib/jvm/java-17-openjdk-amd64\n    java.io.tmpdir = /tmp\n";
+
+        assert_eq!(
+            Some(PathBuf::from("/usr/lib/jvm/java-17-openjdk-amd64")),
+            java_home_from_settings(settings)
+        );
+        assert_eq!(None, java_home_from_settings("openjdk version \"17\""));
+    }
+
+    #[test]
+    fn should_list_searched_locations() {
+        let java_homes = vec![
+            Err("java (java.home not reported)".to_string()),
+            Ok(PathBuf::from("/does/not/exist")),
+            Ok(PathBuf::from("src")),
+            Ok(PathBuf::from("/does/not/exist")),
+        ];
+
+        match find_libjvm(java_homes) {
+            Err(JvmFunctionError::LibJvmNotFound { searched, .. }) => assert_eq!(
+                vec!["java (java.home not reported)", "/does/not/exist", "src"],
+                searched
+            ),
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn should_stop_at_first_java_home_with_libjvm() {
+        let java_home =
+            std::env::temp_dir().join(format!("adhesive-java-home-{}", std::process::id()));
+        let server = java_home.join("lib").join("server");
+        std::fs::create_dir_all(&server).unwrap();
+        std::fs::write(server.join(LIBJVM_FILE_NAME), []).unwrap();
+
+        // locations after java home with libjvm are not evaluated
+        let java_homes = std::iter::once(Ok(java_home.clone())).chain(std::iter::from_fn(
+            || -> Option<std::result::Result<PathBuf, String>> {
+                panic!("java home should not be evaluated")
+            },
+        ));
+        let result = find_libjvm(java_homes);
+
+        std::fs::remove_dir_all(&java_home).unwrap();
+        assert_eq!(server.join(LIBJVM_FILE_NAME), result.unwrap());
+    }
+}
@@ -0,0 +1,108 @@
+use std::{fmt::Display, str::FromStr};
+
+use datafusion::common::{
+    config::{ConfigExtension, ConfigField, Visit},
+    extensions_options,
+};
+use datafusion::error::{DataFusionError, Result};
+
+extensions_options! {
+    /// Session level options of java functions.
+    ///
+    /// Options are applied to functions created afterwards, each function keeps
+    /// options it has been created with. They can be set with
+    /// `SET adhesive.<option> = <value>` once registered with session:
+    ///
+    /// ```ignore
+    /// let config = SessionConfig::new().with_option_extension(AdhesiveOptions::default());
+    /// ```
+    pub struct AdhesiveOptions {
+        /// What to do when java function fails for a row: `fail`, `null` or `skip`,
+        /// unless function class declares its own policy
+        pub error_policy: ErrorPolicy, default = ErrorPolicy::Fail
+    }
+}
+
+impl ConfigExtension for AdhesiveOptions {
+    const PREFIX: &'static str = "adhesive";
+}
+
+/// What to do when java function fails (throws) for a row.
+///
+/// Applies to row by row functions only.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ErrorPolicy {
+    /// Exception is propagated, failing the query
+    #[default]
+    Fail,
+    /// Null is written for the failed row
+    Null,
+    /// Null is written for the failed row, and failure is counted
+    /// (see [crate::JvmFunctionFactory::failed_rows])
+    Skip,
+}
+
+impl ErrorPolicy {
+    /// policy name as expected by java side
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ErrorPolicy::Fail => "fail",
+            ErrorPolicy::Null => "null",
+            ErrorPolicy::Skip => "skip",
+        }
+    }
+}
+
+impl Display for ErrorPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ErrorPolicy {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fail" => Ok(ErrorPolicy::Fail),
+            "null" => Ok(ErrorPolicy::Null),
+            "skip" => Ok(ErrorPolicy::Skip),
+            _ => Err(DataFusionError::Configuration(format!(
+                "unsupported error policy: [{s}], expected one of: fail, null, skip"
+            ))),
+        }
+    }
+}
+
+impl ConfigField for ErrorPolicy {
+    fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str) {
+        v.some(key, self, description)
+    }
+
+    fn set(&mut self, _key: &str, value: &str) -> Result<()> {
+        *self = value.parse()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use datafusion::common::config::ConfigOptions;
+
+    use super::{AdhesiveOptions, ErrorPolicy};
+
+    #[test]
+    fn should_set_error_policy() -> datafusion::error::Result<()> {
+        let mut config = ConfigOptions::new();
+        config.extensions.insert(AdhesiveOptions::default());
+
+        config.set("adhesive.error_policy", "SKIP")?;
+
+        let options = config.extensions.get::<AdhesiveOptions>().unwrap();
+        assert_eq!(ErrorPolicy::Skip, options.error_policy);
+
+        assert!(config.set("adhesive.error_policy", "ignore").is_err());
+
+        Ok(())
+    }
+}
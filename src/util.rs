@@ -3,6 +3,8 @@ use regex::Regex;
 use crate::JvmFunctionError;
 
 const BASE_PACKAGE: &str = "com.github.milenkovicm.generated";
+/// number of lines added to java code by [update_java_code]
+pub(crate) const GENERATED_LINES: i64 = 1;
 
 fn generate_package_name() -> String {
     let charset = "abcdefghijklmnopqrstuvwxyz";
@@ -37,7 +39,7 @@ pub(crate) fn update_java_code(java_code: &str) -> crate::jvm::Result<(String, S
 
 #[cfg(test)]
 mod test {
-    use crate::util::find_class_name;
+    use crate::util::{find_class_name, update_java_code, GENERATED_LINES};
 
     #[test]
     fn should_find_class_name() {
@@ -49,4 +51,15 @@ mod test {
         "#;
         assert_eq!("ClassName1", find_class_name(code).unwrap())
     }
+
+    #[test]
+    fn should_add_generated_lines() {
+        let code = "public class ClassName1 {}";
+        let (updated_code, _) = update_java_code(code).unwrap();
+
+        assert_eq!(
+            code.lines().count() as i64 + GENERATED_LINES,
+            updated_code.lines().count() as i64
+        )
+    }
 }
@@ -2,12 +2,34 @@
 mod test {
     use std::sync::Arc;
 
-    use adhesive::JvmFunctionFactory;
-    use datafusion::arrow::array::{ArrayRef, Int64Array, RecordBatch};
-    use datafusion::{assert_batches_eq, execution::context::SessionContext};
+    use adhesive::{AdhesiveOptions, JvmFunctionFactory};
+    use datafusion::arrow::array::{ArrayRef, Int64Array, RecordBatch, StringArray};
+    use datafusion::arrow::datatypes::DataType;
+    use datafusion::common::ScalarValue;
+    use datafusion::execution::FunctionRegistry;
+    use datafusion::logical_expr::{ColumnarValue, ScalarFunctionArgs};
+    use datafusion::{
+        assert_batches_eq,
+        execution::context::{SessionConfig, SessionContext},
+    };
 
     const JAR_PATH: &str = "java/target/adhesive-jar-with-dependencies.jar";
 
+    /// creates context with java function factory and
+    /// table `t` with two BIGINT columns `a` and `b`
+    fn create_context() -> datafusion::error::Result<SessionContext> {
+        let ctx = SessionContext::new()
+            .with_function_factory(Arc::new(JvmFunctionFactory::new_with_jar(JAR_PATH)?));
+
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3, 4]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![10, 20, 30, 40]));
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)])?;
+
+        ctx.register_batch("t", batch)?;
+
+        Ok(ctx)
+    }
+
     #[tokio::test]
     async fn should_invoke_java() -> datafusion::error::Result<()> {
         let ctx = SessionContext::new()
@@ -38,7 +60,7 @@ mod test {
 
         let result = ctx.sql("select f1(a,b) from t").await?.collect().await?;
 
-        let expected = vec![
+        let expected = [
             "+-------------+",
             "| f1(t.a,t.b) |",
             "+-------------+",
@@ -62,7 +84,7 @@ mod test {
 
         let result = ctx.sql("select f2(a,b) from t").await?.collect().await?;
 
-        let expected = vec![
+        let expected = [
             "+-------------+",
             "| f2(t.a,t.b) |",
             "+-------------+",
@@ -76,4 +98,1850 @@ mod test {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn should_return_int() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_int(BIGINT, BIGINT)
+        RETURNS INT
+        LANGUAGE JAVA
+        AS '
+        public class IntClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Integer compute(org.apache.arrow.vector.table.Row row) {
+                return (int) (row.getBigInt(0) + row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_int(a,b)) as t, f_int(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+-------+----+",
+            "| t     | r  |",
+            "+-------+----+",
+            "| Int32 | 11 |",
+            "| Int32 | 22 |",
+            "| Int32 | 33 |",
+            "| Int32 | 44 |",
+            "+-------+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_small_int() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_small_int(BIGINT, BIGINT)
+        RETURNS SMALLINT
+        LANGUAGE JAVA
+        AS '
+        public class SmallIntClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Short compute(org.apache.arrow.vector.table.Row row) {
+                return (short) (row.getBigInt(0) + row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_small_int(a,b)) as t, f_small_int(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+-------+----+",
+            "| t     | r  |",
+            "+-------+----+",
+            "| Int16 | 11 |",
+            "| Int16 | 22 |",
+            "| Int16 | 33 |",
+            "| Int16 | 44 |",
+            "+-------+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_tiny_int() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_tiny_int(BIGINT, BIGINT)
+        RETURNS TINYINT
+        LANGUAGE JAVA
+        AS '
+        public class TinyIntClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Byte compute(org.apache.arrow.vector.table.Row row) {
+                return (byte) (row.getBigInt(0) + row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_tiny_int(a,b)) as t, f_tiny_int(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+------+----+",
+            "| t    | r  |",
+            "+------+----+",
+            "| Int8 | 11 |",
+            "| Int8 | 22 |",
+            "| Int8 | 33 |",
+            "| Int8 | 44 |",
+            "+------+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_float() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_float(BIGINT, BIGINT)
+        RETURNS FLOAT
+        LANGUAGE JAVA
+        AS '
+        public class FloatClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Float compute(org.apache.arrow.vector.table.Row row) {
+                return (float) row.getBigInt(0) / 2;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_float(a,b)) as t, f_float(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------+-----+",
+            "| t       | r   |",
+            "+---------+-----+",
+            "| Float32 | 0.5 |",
+            "| Float32 | 1.0 |",
+            "| Float32 | 1.5 |",
+            "| Float32 | 2.0 |",
+            "+---------+-----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_double() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_double(BIGINT, BIGINT)
+        RETURNS DOUBLE
+        LANGUAGE JAVA
+        AS '
+        public class DoubleClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Double compute(org.apache.arrow.vector.table.Row row) {
+                return (double) row.getBigInt(1) / 4;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_double(a,b)) as t, f_double(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------+------+",
+            "| t       | r    |",
+            "+---------+------+",
+            "| Float64 | 2.5  |",
+            "| Float64 | 5.0  |",
+            "| Float64 | 7.5  |",
+            "| Float64 | 10.0 |",
+            "+---------+------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_boolean() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_boolean(BIGINT, BIGINT)
+        RETURNS BOOLEAN
+        LANGUAGE JAVA
+        AS '
+        public class BooleanClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Boolean compute(org.apache.arrow.vector.table.Row row) {
+                return row.getBigInt(0) % 2 == 0;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_boolean(a,b)) as t, f_boolean(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------+-------+",
+            "| t       | r     |",
+            "+---------+-------+",
+            "| Boolean | false |",
+            "| Boolean | true  |",
+            "| Boolean | false |",
+            "| Boolean | true  |",
+            "+---------+-------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_varchar() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let s: ArrayRef = Arc::new(StringArray::from(vec!["Alpha", "beta", "GAMMA"]));
+        let batch = RecordBatch::try_from_iter(vec![("s", s)])?;
+        ctx.register_batch("s", batch)?;
+
+        let sql = r#"
+        CREATE FUNCTION f_varchar(VARCHAR)
+        RETURNS VARCHAR
+        LANGUAGE JAVA
+        AS '
+        public class VarCharClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public String compute(org.apache.arrow.vector.table.Row row) {
+                return row.getVarCharObj(0).toLowerCase();
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_varchar(s)) as t, f_varchar(s) as r from s")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+------+-------+",
+            "| t    | r     |",
+            "+------+-------+",
+            "| Utf8 | alpha |",
+            "| Utf8 | beta  |",
+            "| Utf8 | gamma |",
+            "+------+-------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_binary() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_binary(BIGINT, BIGINT)
+        RETURNS BYTEA
+        LANGUAGE JAVA
+        AS '
+        public class BinaryClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public byte[] compute(org.apache.arrow.vector.table.Row row) {
+                return new byte[] { (byte) row.getBigInt(0), (byte) row.getBigInt(1) };
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_binary(a,b)) as t, f_binary(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+--------+------+",
+            "| t      | r    |",
+            "+--------+------+",
+            "| Binary | 010a |",
+            "| Binary | 0214 |",
+            "| Binary | 031e |",
+            "| Binary | 0428 |",
+            "+--------+------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_date() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_date(BIGINT, BIGINT)
+        RETURNS DATE
+        LANGUAGE JAVA
+        AS '
+        public class DateClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.time.LocalDate compute(org.apache.arrow.vector.table.Row row) {
+                return java.time.LocalDate.of(2024, 1, (int) row.getBigInt(0));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_date(a,b)) as t, f_date(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+--------+------------+",
+            "| t      | r          |",
+            "+--------+------------+",
+            "| Date32 | 2024-01-01 |",
+            "| Date32 | 2024-01-02 |",
+            "| Date32 | 2024-01-03 |",
+            "| Date32 | 2024-01-04 |",
+            "+--------+------------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_timestamp_with_time_zone() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_timestamp(BIGINT, BIGINT)
+        RETURNS TIMESTAMP WITH TIME ZONE
+        LANGUAGE JAVA
+        AS '
+        public class TimestampClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.time.Instant compute(org.apache.arrow.vector.table.Row row) {
+                return java.time.Instant.ofEpochSecond(row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_timestamp(a,b)) as t, f_timestamp(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------------------------------------+----------------------+",
+            "| t                                     | r                    |",
+            "+---------------------------------------+----------------------+",
+            "| Timestamp(Nanosecond, Some(\"+00:00\")) | 1970-01-01T00:00:10Z |",
+            "| Timestamp(Nanosecond, Some(\"+00:00\")) | 1970-01-01T00:00:20Z |",
+            "| Timestamp(Nanosecond, Some(\"+00:00\")) | 1970-01-01T00:00:30Z |",
+            "| Timestamp(Nanosecond, Some(\"+00:00\")) | 1970-01-01T00:00:40Z |",
+            "+---------------------------------------+----------------------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_time() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_time(BIGINT, BIGINT)
+        RETURNS TIME
+        LANGUAGE JAVA
+        AS '
+        public class TimeClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.time.LocalTime compute(org.apache.arrow.vector.table.Row row) {
+                return java.time.LocalTime.of((int) row.getBigInt(0), (int) row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_time(a,b)) as t, f_time(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+--------------------+----------+",
+            "| t                  | r        |",
+            "+--------------------+----------+",
+            "| Time64(Nanosecond) | 01:10:00 |",
+            "| Time64(Nanosecond) | 02:20:00 |",
+            "| Time64(Nanosecond) | 03:30:00 |",
+            "| Time64(Nanosecond) | 04:40:00 |",
+            "+--------------------+----------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_interval() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_interval(BIGINT, BIGINT)
+        RETURNS INTERVAL
+        LANGUAGE JAVA
+        AS '
+        public class IntervalClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.time.Duration compute(org.apache.arrow.vector.table.Row row) {
+                return java.time.Duration.ofMinutes(row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_interval(a,b)) as t, f_interval(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+------------------------+---------+",
+            "| t                      | r       |",
+            "+------------------------+---------+",
+            "| Interval(MonthDayNano) | 10 mins |",
+            "| Interval(MonthDayNano) | 20 mins |",
+            "| Interval(MonthDayNano) | 30 mins |",
+            "| Interval(MonthDayNano) | 40 mins |",
+            "+------------------------+---------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_decimal() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        // result is rescaled from scale 2 to scale 3
+        let sql = r#"
+        CREATE FUNCTION f_decimal(DECIMAL(10, 2), BIGINT)
+        RETURNS DECIMAL(12, 3)
+        LANGUAGE JAVA
+        AS '
+        public class DecimalClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.math.BigDecimal compute(org.apache.arrow.vector.table.Row row) {
+                return row.getDecimalObj(0)
+                    .multiply(java.math.BigDecimal.valueOf(row.getBigInt(1)))
+                    .divide(java.math.BigDecimal.valueOf(3), java.math.RoundingMode.HALF_UP);
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_decimal(cast(a as decimal(10, 2)), b)) as t, f_decimal(cast(a as decimal(10, 2)), b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+-------------------+--------+",
+            "| t                 | r      |",
+            "+-------------------+--------+",
+            "| Decimal128(12, 3) | 3.330  |",
+            "| Decimal128(12, 3) | 13.330 |",
+            "| Decimal128(12, 3) | 30.000 |",
+            "| Decimal128(12, 3) | 53.330 |",
+            "+-------------------+--------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_decimal256() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_decimal256(BIGINT, BIGINT)
+        RETURNS DECIMAL(40, 2)
+        LANGUAGE JAVA
+        AS '
+        public class Decimal256Class extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.math.BigDecimal compute(org.apache.arrow.vector.table.Row row) {
+                return java.math.BigDecimal.valueOf(row.getBigInt(0), 1);
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select arrow_typeof(f_decimal256(a, b)) as t, f_decimal256(a, b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+-------------------+------+",
+            "| t                 | r    |",
+            "+-------------------+------+",
+            "| Decimal256(40, 2) | 0.10 |",
+            "| Decimal256(40, 2) | 0.20 |",
+            "| Decimal256(40, 2) | 0.30 |",
+            "| Decimal256(40, 2) | 0.40 |",
+            "+-------------------+------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_decimal_out_of_precision() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_decimal_overflow(BIGINT, BIGINT)
+        RETURNS DECIMAL(3, 2)
+        LANGUAGE JAVA
+        AS '
+        public class DecimalOverflowClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.math.BigDecimal compute(org.apache.arrow.vector.table.Row row) {
+                return java.math.BigDecimal.valueOf(row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_decimal_overflow(a, b) from t")
+            .await?
+            .collect()
+            .await;
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_list() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_list(BIGINT, BIGINT)
+        RETURNS BIGINT[]
+        LANGUAGE JAVA
+        AS '
+        public class ListClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public java.util.List<Long> compute(org.apache.arrow.vector.table.Row row) {
+                return java.util.List.of(row.getBigInt(0), row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_list(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------+",
+            "| r       |",
+            "+---------+",
+            "| [1, 10] |",
+            "| [2, 20] |",
+            "| [3, 30] |",
+            "| [4, 40] |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_struct() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_struct(BIGINT, BIGINT)
+        RETURNS STRUCT<a BIGINT, b VARCHAR>
+        LANGUAGE JAVA
+        AS '
+        public class StructClass extends com.github.milenkovicm.adhesive.Adhesive {
+            public record Result(Long a, String b) {}
+
+            @Override
+            public Result compute(org.apache.arrow.vector.table.Row row) {
+                return new Result(row.getBigInt(0), "b" + row.getBigInt(1));
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_struct(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----------------+",
+            "| r              |",
+            "+----------------+",
+            "| {a: 1, b: b10} |",
+            "| {a: 2, b: b20} |",
+            "| {a: 3, b: b30} |",
+            "| {a: 4, b: b40} |",
+            "+----------------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_map() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        // SQL can't declare map type, it is declared by function class
+        let sql = r#"
+        CREATE FUNCTION f_map()
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.MapExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_map(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------------+",
+            "| r             |",
+            "+---------------+",
+            "| {a: 1, b: 10} |",
+            "| {a: 2, b: 20} |",
+            "| {a: 3, b: 30} |",
+            "| {a: 4, b: 40} |",
+            "+---------------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_handle_null_arguments() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3), None]));
+        let b: ArrayRef = Arc::new(Int64Array::from(vec![Some(10), Some(20), None, None]));
+        let batch = RecordBatch::try_from_iter(vec![("a", a), ("b", b)])?;
+        ctx.register_batch("n", batch)?;
+
+        let sql = r#"
+        CREATE FUNCTION f_null(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class NullClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Long compute(org.apache.arrow.vector.table.Row row) {
+                var a = row.isNull(0) ? 0 : row.getBigInt(0);
+                var b = row.isNull(1) ? 0 : row.getBigInt(1);
+                return a + b;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // strict function would fail on null argument
+        // if it was invoked
+        let sql = r#"
+        CREATE FUNCTION f_strict(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class StrictClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public boolean isStrict() {
+                return true;
+            }
+
+            @Override
+            public Long compute(org.apache.arrow.vector.table.Row row) {
+                return row.getBigInt(0) + row.getBigInt(1);
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_null(a,b) as r, f_strict(a,b) as s from n")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----+----+",
+            "| r  | s  |",
+            "+----+----+",
+            "| 11 | 11 |",
+            "| 20 |    |",
+            "| 3  |    |",
+            "| 0  |    |",
+            "+----+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_vectorized_java() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_vectorized(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.VectorizedExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let sql = r#"
+        CREATE FUNCTION f_vectorized_java(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class VectorizedClass extends com.github.milenkovicm.adhesive.VectorizedAdhesive {
+            @Override
+            protected org.apache.arrow.vector.FieldVector compute(
+                org.apache.arrow.vector.table.Table arguments,
+                org.apache.arrow.memory.BufferAllocator allocator) {
+                var a = (org.apache.arrow.vector.BigIntVector) arguments.getVector(0);
+                var b = (org.apache.arrow.vector.BigIntVector) arguments.getVector(1);
+                var result = new org.apache.arrow.vector.BigIntVector("result", allocator);
+                result.allocateNew(a.getValueCount());
+                for (int i = 0; i < a.getValueCount(); i++) {
+                    result.set(i, a.get(i) * b.get(i));
+                }
+                result.setValueCount(a.getValueCount());
+                return result;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_vectorized(a,b) as r, f_vectorized_java(a,b) as s from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----+-----+",
+            "| r  | s   |",
+            "+----+-----+",
+            "| 11 | 10  |",
+            "| 22 | 40  |",
+            "| 33 | 90  |",
+            "| 44 | 160 |",
+            "+----+-----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_function_without_arguments() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_answer()
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class AnswerClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Long compute(org.apache.arrow.vector.table.Row row) {
+                return 42L;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let sql = r#"
+        CREATE FUNCTION f_row_number()
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class RowNumberClass extends com.github.milenkovicm.adhesive.VectorizedAdhesive {
+            @Override
+            protected org.apache.arrow.vector.FieldVector compute(
+                org.apache.arrow.vector.table.Table arguments,
+                org.apache.arrow.memory.BufferAllocator allocator) {
+                var rowCount = (int) arguments.getRowCount();
+                var result = new org.apache.arrow.vector.BigIntVector("result", allocator);
+                result.allocateNew(rowCount);
+                for (int i = 0; i < rowCount; i++) {
+                    result.set(i, i + 1);
+                }
+                result.setValueCount(rowCount);
+                return result;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // function is invoked for every row of a batch,
+        // even though it has no arguments
+        let result = ctx
+            .sql("select a, f_answer() as x, f_row_number() as n from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---+----+---+",
+            "| a | x  | n |",
+            "+---+----+---+",
+            "| 1 | 42 | 1 |",
+            "| 2 | 42 | 2 |",
+            "| 3 | 42 | 3 |",
+            "| 4 | 42 | 4 |",
+            "+---+----+---+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_vectorized_result_of_wrong_type() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_vectorized_int(BIGINT, BIGINT)
+        RETURNS INT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.VectorizedExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_vectorized_int(a,b) from t")
+            .await?
+            .collect()
+            .await;
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_accept_vectorized_result_with_other_field_names(
+    ) -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        // java list vector names its items `$data$`, rather than `item`
+        let sql = r#"
+        CREATE FUNCTION f_vectorized_list(BIGINT, BIGINT)
+        RETURNS BIGINT[]
+        LANGUAGE JAVA
+        AS '
+        public class VectorizedListClass extends com.github.milenkovicm.adhesive.VectorizedAdhesive {
+            @Override
+            protected org.apache.arrow.vector.FieldVector compute(
+                org.apache.arrow.vector.table.Table arguments,
+                org.apache.arrow.memory.BufferAllocator allocator) {
+                var a = (org.apache.arrow.vector.BigIntVector) arguments.getVector(0);
+                var b = (org.apache.arrow.vector.BigIntVector) arguments.getVector(1);
+                var result = org.apache.arrow.vector.complex.ListVector.empty("result", allocator);
+                var writer = result.getWriter();
+                for (int i = 0; i < a.getValueCount(); i++) {
+                    writer.setPosition(i);
+                    writer.startList();
+                    writer.bigInt().writeBigInt(a.get(i));
+                    writer.bigInt().writeBigInt(b.get(i));
+                    writer.endList();
+                }
+                result.setValueCount(a.getValueCount());
+                return result;
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_vectorized_list(a,b) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---------+",
+            "| r       |",
+            "+---------+",
+            "| [1, 10] |",
+            "| [2, 20] |",
+            "| [3, 30] |",
+            "| [4, 40] |",
+            "+---------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_java_aggregate() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_sum(BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.SumExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_sum(a) as s from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = ["+----+", "| s  |", "+----+", "| 10 |", "+----+"];
+        assert_batches_eq!(expected, &result);
+
+        let result = ctx
+            .sql("select a % 2 as k, f_sum(b) as s from t group by k order by k")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---+----+",
+            "| k | s  |",
+            "+---+----+",
+            "| 0 | 60 |",
+            "| 1 | 40 |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_return_time_and_interval_from_aggregate() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        // sum of minutes, as time of day
+        let sql = r#"
+        CREATE FUNCTION f_sum_time(BIGINT)
+        RETURNS TIME
+        LANGUAGE JAVA
+        AS '
+        public class SumTimeClass extends com.github.milenkovicm.adhesive.AdhesiveAccumulator {
+            private long minutes = 0;
+
+            @Override
+            protected void update(org.apache.arrow.vector.table.Row row) {
+                minutes += row.getBigInt(0);
+            }
+
+            @Override
+            protected void merge(byte[] state) {
+                minutes += java.nio.ByteBuffer.wrap(state).getLong();
+            }
+
+            @Override
+            protected byte[] state() {
+                return java.nio.ByteBuffer.allocate(Long.BYTES).putLong(minutes).array();
+            }
+
+            @Override
+            protected java.time.LocalTime evaluate() {
+                return java.time.LocalTime.MIDNIGHT.plusMinutes(minutes);
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // sum of minutes, as interval
+        let sql = r#"
+        CREATE FUNCTION f_sum_interval(BIGINT)
+        RETURNS INTERVAL
+        LANGUAGE JAVA
+        AS '
+        public class SumIntervalClass extends com.github.milenkovicm.adhesive.AdhesiveAccumulator {
+            private long minutes = 0;
+
+            @Override
+            protected void update(org.apache.arrow.vector.table.Row row) {
+                minutes += row.getBigInt(0);
+            }
+
+            @Override
+            protected void merge(byte[] state) {
+                minutes += java.nio.ByteBuffer.wrap(state).getLong();
+            }
+
+            @Override
+            protected byte[] state() {
+                return java.nio.ByteBuffer.allocate(Long.BYTES).putLong(minutes).array();
+            }
+
+            @Override
+            protected java.time.Duration evaluate() {
+                return java.time.Duration.ofMinutes(minutes);
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_sum_time(b) as t, f_sum_interval(b) as i from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----------+----------+",
+            "| t        | i        |",
+            "+----------+----------+",
+            "| 01:40:00 | 100 mins |",
+            "+----------+----------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_java_window_function() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let ts: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 10, 11, 30]));
+        let batch = RecordBatch::try_from_iter(vec![("ts", ts)])?;
+        ctx.register_batch("e", batch)?;
+
+        let sql = r#"
+        CREATE FUNCTION f_session(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.SessionExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select ts, f_session(ts, 5) over (order by ts) as s from e")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----+---+",
+            "| ts | s |",
+            "+----+---+",
+            "| 1  | 0 |",
+            "| 2  | 0 |",
+            "| 10 | 1 |",
+            "| 11 | 1 |",
+            "| 30 | 2 |",
+            "+----+---+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_java_window_function_with_frame() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_frame_sum(BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.FrameSumExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select a, f_frame_sum(b) over (order by a rows between 1 preceding and current row) as s from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---+----+",
+            "| a | s  |",
+            "+---+----+",
+            "| 1 | 10 |",
+            "| 2 | 30 |",
+            "| 3 | 50 |",
+            "| 4 | 70 |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_java_table_function() -> datafusion::error::Result<()> {
+        let factory = Arc::new(JvmFunctionFactory::new_with_jar(JAR_PATH)?);
+        let ctx = SessionContext::new().with_function_factory(factory.clone());
+
+        // table functions can't be created using sql
+        ctx.register_udtf(
+            "f_range",
+            factory
+                .create_table_function("com.github.milenkovicm.adhesive.example.RangeExample")?,
+        );
+
+        let result = ctx.sql("select * from f_range(6)").await?.collect().await?;
+
+        let expected = [
+            "+-------+",
+            "| value |",
+            "+-------+",
+            "| 0     |",
+            "| 1     |",
+            "| 2     |",
+            "| 3     |",
+            "| 4     |",
+            "| 5     |",
+            "+-------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        let sql = r#"
+        CREATE FUNCTION f_range_sql(BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.RangeExample"
+        "#;
+
+        assert!(ctx.sql(sql).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_verify_argument_types() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_repeat(BIGINT, VARCHAR)
+        RETURNS VARCHAR
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.RepeatExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_repeat(a, 'x') as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+------+", //
+            "| r    |", //
+            "+------+", //
+            "| x    |", //
+            "| xx   |", //
+            "| xxx  |", //
+            "| xxxx |", //
+            "+------+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        let sql = r#"
+        CREATE FUNCTION f_repeat_invalid(BIGINT, BIGINT)
+        RETURNS VARCHAR
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.RepeatExample"
+        "#;
+
+        let error = ctx
+            .sql(sql)
+            .await
+            .expect_err("argument type mismatch expected");
+        assert!(
+            error
+                .to_string()
+                .contains("expects arguments (Int64, Utf8), got (Int64, Int64)"),
+            "unexpected error: {error}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_use_declared_signature() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_concat()
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.ConcatExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_concat('a', cast(a as varchar)) as r from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----+", //
+            "| r  |", //
+            "+----+", //
+            "| a1 |", //
+            "| a2 |", //
+            "| a3 |", //
+            "| a4 |", //
+            "+----+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        let sql = r#"
+        CREATE FUNCTION f_concat_invalid(VARCHAR, VARCHAR)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.ConcatExample"
+        "#;
+
+        let error = ctx
+            .sql(sql)
+            .await
+            .expect_err("return type mismatch expected");
+        assert!(
+            error.to_string().contains("returns Utf8, got Int64"),
+            "unexpected error: {error}"
+        );
+
+        // basic example does not declare signature
+        let sql = r#"
+        CREATE FUNCTION f_basic()
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        assert!(ctx.sql(sql).await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_overloaded_function() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_overloaded(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let sql = r#"
+        CREATE FUNCTION f_overloaded(VARCHAR, VARCHAR)
+        RETURNS VARCHAR
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.ConcatExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql(
+                "select f_overloaded(a, b) as n, f_overloaded('x', cast(a as varchar)) as s from t",
+            )
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----+----+", //
+            "| n  | s  |", //
+            "+----+----+", //
+            "| 11 | x1 |", //
+            "| 22 | x2 |", //
+            "| 33 | x3 |", //
+            "| 44 | x4 |", //
+            "+----+----+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        // no overload accepts three arguments
+        let result = ctx.sql("select f_overloaded(a, b, a) from t").await;
+        assert!(result.is_err());
+
+        // existing overload is not replaced, unless requested
+        let sql = r#"
+        CREATE FUNCTION f_overloaded(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        let error = ctx.sql(sql).await.unwrap_err().to_string();
+        assert!(
+            error.contains("already exists"),
+            "unexpected error: {error}"
+        );
+
+        // replaced function has no overloads,
+        // so arguments are coerced to VARCHAR
+        let sql = r#"
+        CREATE OR REPLACE FUNCTION f_overloaded(VARCHAR, VARCHAR)
+        RETURNS VARCHAR
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.ConcatExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_overloaded(a, b) as s from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+-----+", //
+            "| s   |", //
+            "+-----+", //
+            "| 110 |", //
+            "| 220 |", //
+            "| 330 |", //
+            "| 440 |", //
+            "+-----+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_variadic_function() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_sum_all()
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.SumAllExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // integer arguments are coerced to BIGINT
+        let result = ctx
+            .sql("select f_sum_all(a) as s1, f_sum_all(a, b, cast(1 as int)) as s3 from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+----+----+", //
+            "| s1 | s3 |", //
+            "+----+----+", //
+            "| 1  | 12 |", //
+            "| 2  | 23 |", //
+            "| 3  | 34 |", //
+            "| 4  | 45 |", //
+            "+----+----+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_fold_immutable_function() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_immutable(BIGINT, BIGINT)
+        RETURNS BIGINT
+        IMMUTABLE
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let sql = r#"
+        CREATE FUNCTION f_volatile(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // immutable function with literal arguments is
+        // invoked once, when query is planned
+        let plan = ctx
+            .sql("select f_immutable(1, 2) as i, f_volatile(1, 2) as v")
+            .await?
+            .into_optimized_plan()?
+            .to_string();
+
+        assert!(!plan.contains("f_immutable"), "unexpected plan: {plan}");
+        assert!(plan.contains("Int64(3) AS i"), "unexpected plan: {plan}");
+        assert!(plan.contains("f_volatile"), "unexpected plan: {plan}");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_function_with_scalar_arguments() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_stable(BIGINT, BIGINT)
+        RETURNS BIGINT
+        STABLE
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // stable function is not folded when query is planned, so it is
+        // invoked with scalar arguments; java is invoked for a single row
+        // and scalar result is returned, rather than a result per row
+        let udf = ctx.udf("f_stable")?;
+        let result = udf.invoke_with_args(ScalarFunctionArgs {
+            args: vec![
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(1))),
+                ColumnarValue::Scalar(ScalarValue::Int64(Some(2))),
+            ],
+            number_rows: 4,
+            return_type: &DataType::Int64,
+        })?;
+
+        match result {
+            ColumnarValue::Scalar(value) => assert_eq!(ScalarValue::Int64(Some(3)), value),
+            ColumnarValue::Array(array) => panic!("expected scalar result, got: {array:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_invoke_function_with_column_and_literal_arguments(
+    ) -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_add(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.BasicExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // vectorized function accepting constants gets
+        // literal argument as a single value
+        let sql = r#"
+        CREATE FUNCTION f_multiply()
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.MultiplyExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_add(a, 1) as s, f_multiply(a, 10) as m, f_multiply(100, b) as n from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+---+----+------+", //
+            "| s | m  | n    |", //
+            "+---+----+------+", //
+            "| 2 | 10 | 1000 |", //
+            "| 3 | 20 | 2000 |", //
+            "| 4 | 30 | 3000 |", //
+            "| 5 | 40 | 4000 |", //
+            "+---+----+------+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_compute_return_type() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_scale()
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.ScaleExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        // return type scale is given by literal argument
+        let df = ctx.sql("select f_scale(a, 2) as r from t").await?;
+
+        assert_eq!(
+            &datafusion::arrow::datatypes::DataType::Decimal128(20, 2),
+            df.schema().field(0).data_type()
+        );
+
+        let result = df.collect().await?;
+
+        let expected = [
+            "+------+", //
+            "| r    |", //
+            "+------+", //
+            "| 1.00 |", //
+            "| 2.00 |", //
+            "| 3.00 |", //
+            "| 4.00 |", //
+            "+------+", //
+        ];
+
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_report_java_exception() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_freaks_out(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE CLASS
+        AS "com.github.milenkovicm.adhesive.example.FreaksOutExample"
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_freaks_out(a,b) from t")
+            .await?
+            .collect()
+            .await;
+
+        let error = result.expect_err("java exception expected").to_string();
+        assert!(
+            error.contains("java.lang.RuntimeException: Its ok to freak out sometimes"),
+            "unexpected error: {error}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_report_compilation_errors() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        // semicolon is missing on line 5
+        let sql = r#"
+        CREATE FUNCTION f_broken(BIGINT, BIGINT)
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class NewClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Long compute(org.apache.arrow.vector.table.Row row) {
+                return row.getBigInt(0) * row.getBigInt(1)
+            }
+        }
+        '
+        "#;
+
+        let error = ctx
+            .sql(sql)
+            .await
+            .expect_err("compilation error expected")
+            .to_string();
+
+        assert!(
+            error.contains("ERROR at line 5"),
+            "unexpected error: {error}"
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_apply_error_policy() -> datafusion::error::Result<()> {
+        let factory = Arc::new(JvmFunctionFactory::new_with_jar(JAR_PATH)?);
+        let config = SessionConfig::new().with_option_extension(AdhesiveOptions::default());
+        let ctx = SessionContext::new_with_config(config).with_function_factory(factory.clone());
+
+        let a: ArrayRef = Arc::new(Int64Array::from(vec![1, 2, 3, 4]));
+        let batch = RecordBatch::try_from_iter(vec![("a", a)])?;
+        ctx.register_batch("t", batch)?;
+
+        let java_code = r#"
+        public class NewClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public Long compute(org.apache.arrow.vector.table.Row row) {
+                if (row.getBigInt(0) % 2 == 0) {
+                    throw new IllegalArgumentException("even numbers not supported");
+                }
+                return row.getBigInt(0);
+            }
+        }
+        "#;
+
+        // policy is applied when function is created
+        ctx.sql("SET adhesive.error_policy = 'fail'").await?;
+        ctx.sql(&format!(
+            "CREATE FUNCTION f_fail(BIGINT) RETURNS BIGINT LANGUAGE JAVA AS '{java_code}'"
+        ))
+        .await?;
+
+        ctx.sql("SET adhesive.error_policy = 'skip'").await?;
+        ctx.sql(&format!(
+            "CREATE FUNCTION f_skip(BIGINT) RETURNS BIGINT LANGUAGE JAVA AS '{java_code}'"
+        ))
+        .await?;
+
+        let result = ctx.sql("select f_fail(a) from t").await?.collect().await;
+        assert!(result.is_err());
+
+        let result = ctx
+            .sql("select f_skip(a) as result from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+--------+",
+            "| result |",
+            "+--------+",
+            "| 1      |",
+            "|        |",
+            "| 3      |",
+            "|        |",
+            "+--------+",
+        ];
+        assert_batches_eq!(expected, &result);
+        assert_eq!(2, factory.failed_rows("f_skip"));
+
+        // overloads count their failed rows separately
+        ctx.sql(&format!(
+            "CREATE FUNCTION f_skip(BIGINT, BIGINT) RETURNS BIGINT LANGUAGE JAVA AS '{java_code}'"
+        ))
+        .await?;
+        ctx.sql("select f_skip(a, a) as result from t")
+            .await?
+            .collect()
+            .await?;
+
+        assert_eq!(4, factory.failed_rows("f_skip"));
+        assert_eq!(
+            2,
+            factory.overload_failed_rows("f_skip", &[DataType::Int64, DataType::Int64])
+        );
+        assert_eq!(
+            2,
+            factory.overload_failed_rows("f_skip", &[DataType::Int64])
+        );
+
+        // existing functions keep their policy
+        ctx.sql("SET adhesive.error_policy = 'null'").await?;
+        let result = ctx.sql("select f_fail(a) from t").await?.collect().await;
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_apply_declared_error_policy() -> datafusion::error::Result<()> {
+        let ctx = create_context()?;
+
+        let sql = r#"
+        CREATE FUNCTION f_lenient(BIGINT)
+        RETURNS BIGINT
+        LANGUAGE JAVA
+        AS '
+        public class LenientClass extends com.github.milenkovicm.adhesive.Adhesive {
+            @Override
+            public com.github.milenkovicm.adhesive.ErrorPolicy errorPolicy() {
+                return com.github.milenkovicm.adhesive.ErrorPolicy.NULL;
+            }
+
+            @Override
+            public Long compute(org.apache.arrow.vector.table.Row row) {
+                if (row.getBigInt(0) % 2 == 0) {
+                    throw new IllegalArgumentException("even numbers not supported");
+                }
+                return row.getBigInt(0);
+            }
+        }
+        '
+        "#;
+
+        ctx.sql(sql).await?.show().await?;
+
+        let result = ctx
+            .sql("select f_lenient(a) as result from t")
+            .await?
+            .collect()
+            .await?;
+
+        let expected = [
+            "+--------+",
+            "| result |",
+            "+--------+",
+            "| 1      |",
+            "|        |",
+            "| 3      |",
+            "|        |",
+            "+--------+",
+        ];
+        assert_batches_eq!(expected, &result);
+
+        Ok(())
+    }
 }